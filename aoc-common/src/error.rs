@@ -0,0 +1,183 @@
+use std::fmt;
+
+/// What went wrong while parsing a puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    Expected { expected: String, found: String },
+    UnexpectedEnd { expected: String },
+    InvalidNumber { found: String, reason: String },
+    Invalid { reason: String, found: String },
+}
+
+/// A parse failure and where in the input it happened. Lines and columns are
+/// 1-based and columns count characters, not bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+/// Finds the line and column that `fragment` starts at within `s`.
+///
+/// `fragment` must be a slice of `s`, as handed out by `lines`, `split` and
+/// friends.
+fn locate(s: &str, fragment: &str) -> (usize, usize) {
+    let offset = (fragment.as_ptr() as usize).wrapping_sub(s.as_ptr() as usize);
+    debug_assert!(
+        offset <= s.len(),
+        "fragment isn't part of the parsed string"
+    );
+    if offset > s.len() || !s.is_char_boundary(offset) {
+        return (1, 1);
+    }
+
+    let before = &s[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+impl ParseError {
+    fn at(s: &str, fragment: &str, kind: ParseErrorKind) -> ParseError {
+        let (line, column) = locate(s, fragment);
+        ParseError { line, column, kind }
+    }
+
+    /// `fragment` of `s` isn't what we were `expected` to find there.
+    pub fn expected(s: &str, fragment: &str, expected: impl Into<String>) -> ParseError {
+        let kind = ParseErrorKind::Expected {
+            expected: expected.into(),
+            found: fragment.into(),
+        };
+        ParseError::at(s, fragment, kind)
+    }
+
+    /// `s` ran out before we found what was `expected`.
+    pub fn unexpected_end(s: &str, expected: impl Into<String>) -> ParseError {
+        let kind = ParseErrorKind::UnexpectedEnd {
+            expected: expected.into(),
+        };
+        ParseError::at(s, &s[s.len()..], kind)
+    }
+
+    /// `fragment` of `s` is well formed but doesn't make sense.
+    pub fn invalid(s: &str, fragment: &str, reason: impl Into<String>) -> ParseError {
+        let kind = ParseErrorKind::Invalid {
+            reason: reason.into(),
+            found: fragment.into(),
+        };
+        ParseError::at(s, fragment, kind)
+    }
+
+    /// Moves an error reported against `inner` to its position within `outer`,
+    /// for when a parser hands a slice of its input to another parser.
+    pub fn within(self, outer: &str, inner: &str) -> ParseError {
+        let (line, column) = locate(outer, inner);
+        ParseError {
+            line: self.line + line - 1,
+            column: if self.line == 1 {
+                self.column + column - 1
+            } else {
+                self.column
+            },
+            kind: self.kind,
+        }
+    }
+}
+
+/// Parses `fragment` of `s` as a number.
+pub fn parse_number<T>(s: &str, fragment: &str) -> Result<T, ParseError>
+where
+    T: std::str::FromStr,
+    T::Err: fmt::Display,
+{
+    fragment.parse().map_err(|err: T::Err| {
+        let kind = ParseErrorKind::InvalidNumber {
+            found: fragment.into(),
+            reason: err.to_string(),
+        };
+        ParseError::at(s, fragment, kind)
+    })
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::Expected { expected, found } => {
+                write!(f, "expected {expected}, found '{found}'")
+            }
+            ParseErrorKind::UnexpectedEnd { expected } => {
+                write!(f, "expected {expected}, found end of input")
+            }
+            ParseErrorKind::InvalidNumber { found, reason } => {
+                write!(f, "invalid number '{found}': {reason}")
+            }
+            ParseErrorKind::Invalid { reason, found } => write!(f, "{reason}: '{found}'"),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.kind)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TEST: &str = "LR\n\nAAA = (BBB, XXX)\nBBB = (ÅÅÅ, ZZZ)\n";
+
+    #[test]
+    fn test_locate() {
+        assert_eq!(locate(TEST, TEST), (1, 1));
+        assert_eq!(locate(TEST, &TEST[1..]), (1, 2));
+        assert_eq!(locate(TEST, TEST.lines().nth(2).unwrap()), (3, 1));
+
+        let line = TEST.lines().nth(3).unwrap();
+        let (_, nodes) = line.split_once(", ").unwrap();
+        assert_eq!(locate(TEST, nodes), (4, 13));
+        assert_eq!(locate(TEST, &TEST[TEST.len()..]), (5, 1));
+    }
+
+    #[test]
+    fn test_display() {
+        let line = TEST.lines().nth(2).unwrap();
+        let err = ParseError::expected(TEST, &line[12..15], "an existing node");
+        assert_eq!(
+            err.to_string(),
+            "3:13: expected an existing node, found 'XXX'"
+        );
+
+        let err = ParseError::unexpected_end(TEST, "a blank line");
+        assert_eq!(
+            err.to_string(),
+            "5:1: expected a blank line, found end of input"
+        );
+
+        let err = parse_number::<u32>(TEST, &TEST[..2]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "1:1: invalid number 'LR': invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn test_within() {
+        let line = TEST.lines().nth(2).unwrap();
+        let (_, node) = line.split_once(" = ").unwrap();
+
+        let err = ParseError::expected(node, &node[1..4], "a node").within(TEST, node);
+        assert_eq!((err.line, err.column), (3, 8));
+
+        let (_, nodes) = TEST.split_once("\n\n").unwrap();
+        let second = nodes.lines().nth(1).unwrap();
+        let err = ParseError::expected(nodes, &second[2..], "a node").within(TEST, nodes);
+        assert_eq!((err.line, err.column), (4, 3));
+    }
+}
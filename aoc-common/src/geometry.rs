@@ -0,0 +1,229 @@
+use crate::math::gcd;
+use crate::Position;
+use std::fmt;
+use std::num::TryFromIntError;
+use std::ops::{Add, Sub};
+
+/// A point on the integer plane. `y` grows downwards, like rows in a grid.
+///
+/// `+` and `-` panic on overflow even in release builds rather than wrapping;
+/// use [`Point::checked_add`] and [`Point::checked_sub`] to handle it instead.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Point {
+        Point { x, y }
+    }
+
+    pub fn checked_add(self, other: Point) -> Option<Point> {
+        Some(Point::new(
+            self.x.checked_add(other.x)?,
+            self.y.checked_add(other.y)?,
+        ))
+    }
+
+    pub fn checked_sub(self, other: Point) -> Option<Point> {
+        Some(Point::new(
+            self.x.checked_sub(other.x)?,
+            self.y.checked_sub(other.y)?,
+        ))
+    }
+
+    /// The taxicab distance: steps needed moving only horizontally or
+    /// vertically. `None` if it doesn't fit in a `u64`.
+    pub fn manhattan(self, other: Point) -> Option<u64> {
+        self.x
+            .abs_diff(other.x)
+            .checked_add(self.y.abs_diff(other.y))
+    }
+
+    /// The chessboard distance: steps needed when diagonal moves are allowed
+    /// too. Unlike [`Point::manhattan`] this always fits.
+    pub fn chebyshev(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        self.checked_add(other).expect("point addition overflowed")
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        self.checked_sub(other)
+            .expect("point subtraction overflowed")
+    }
+}
+
+/// A grid position's column becomes `x` and its row `y`.
+impl TryFrom<Position> for Point {
+    type Error = TryFromIntError;
+
+    fn try_from((row, column): Position) -> Result<Point, Self::Error> {
+        Ok(Point::new(column.try_into()?, row.try_into()?))
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// Twice the area enclosed by the polygon with the given `vertices`, in
+/// order, using the shoelace formula. Doubling keeps it an exact integer.
+/// `None` if it overflows a `u64`.
+///
+/// See <https://en.wikipedia.org/wiki/Shoelace_formula>.
+pub fn double_area(vertices: &[Point]) -> Option<u64> {
+    let mut sum: i128 = 0;
+    for (i, a) in vertices.iter().enumerate() {
+        let b = vertices[(i + 1) % vertices.len()];
+        // each product fits in an i128, but their difference might not
+        let cross = (a.x as i128 * b.y as i128).checked_sub(b.x as i128 * a.y as i128)?;
+        sum = sum.checked_add(cross)?;
+    }
+    sum.unsigned_abs().try_into().ok()
+}
+
+/// How many lattice points lie on the edges of the polygon with the given
+/// `vertices`, vertices included. `None` if it overflows a `u64`.
+pub fn boundary_points(vertices: &[Point]) -> Option<u64> {
+    vertices
+        .iter()
+        .enumerate()
+        .try_fold(0_u64, |count, (i, a)| {
+            let b = vertices[(i + 1) % vertices.len()];
+            // an edge passes through one lattice point per step of the gcd, not
+            // counting the vertex it ends on
+            count.checked_add(gcd(a.x.abs_diff(b.x), a.y.abs_diff(b.y)))
+        })
+}
+
+/// How many lattice points lie strictly inside the simple polygon with the
+/// given `vertices`, from its area and boundary by Pick's theorem. `None` on
+/// overflow, if there are fewer than three vertices or they enclose no area,
+/// or if the theorem gives a count that can't be right. Otherwise a polygon
+/// that crosses itself isn't noticed, and gives a meaningless count.
+///
+/// See <https://en.wikipedia.org/wiki/Pick%27s_theorem>.
+pub fn interior_points(vertices: &[Point]) -> Option<u64> {
+    let double_area = double_area(vertices)?;
+    if vertices.len() < 3 || double_area == 0 {
+        return None;
+    }
+
+    // A = i + b/2 - 1, so 2i = 2A - b + 2
+    let double_interior = (double_area as i128) - (boundary_points(vertices)? as i128) + 2;
+    if double_interior < 0 || double_interior % 2 != 0 {
+        return None;
+    }
+    (double_interior / 2).try_into().ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // a 4x3 rectangle with a notch cut out of its top edge
+    const NOTCHED: [Point; 8] = [
+        Point::new(0, 0),
+        Point::new(1, 0),
+        Point::new(1, 1),
+        Point::new(2, 1),
+        Point::new(2, 0),
+        Point::new(4, 0),
+        Point::new(4, 3),
+        Point::new(0, 3),
+    ];
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point::new(3, -4);
+        assert_eq!(a + Point::new(1, 1), Point::new(4, -3));
+        assert_eq!(a - a, Point::ORIGIN);
+        assert_eq!(Point::new(i64::MAX, 0).checked_add(Point::new(1, 0)), None);
+        assert_eq!(Point::new(i64::MIN, 0).checked_sub(Point::new(1, 0)), None);
+        assert_eq!(Point::try_from((2, 5)), Ok(Point::new(5, 2)));
+    }
+
+    #[test]
+    #[should_panic(expected = "point addition overflowed")]
+    fn test_add_overflow() {
+        let _ = Point::new(0, i64::MAX) + Point::new(0, 1);
+    }
+
+    #[test]
+    fn test_distances() {
+        let (a, b) = (Point::new(1, 6), Point::new(5, 11));
+        assert_eq!(a.manhattan(b), Some(9));
+        assert_eq!(a.chebyshev(b), 5);
+
+        let (min, max) = (
+            Point::new(i64::MIN, i64::MIN),
+            Point::new(i64::MAX, i64::MAX),
+        );
+        assert_eq!(min.manhattan(max), None);
+        assert_eq!(min.chebyshev(max), u64::MAX);
+    }
+
+    #[test]
+    fn test_polygon() {
+        assert_eq!(double_area(&NOTCHED), Some(22));
+        assert_eq!(boundary_points(&NOTCHED), Some(16));
+        assert_eq!(interior_points(&NOTCHED), Some(4));
+
+        // the same shape walked the other way round
+        let mut reversed = NOTCHED;
+        reversed.reverse();
+        assert_eq!(interior_points(&reversed), Some(4));
+
+        let triangle = [Point::new(0, 0), Point::new(6, 0), Point::new(0, 4)];
+        assert_eq!(double_area(&triangle), Some(24));
+        assert_eq!(boundary_points(&triangle), Some(6 + 2 + 4));
+        assert_eq!(interior_points(&triangle), Some(7));
+
+        // too few vertices, or none of them off the line, enclose nothing
+        assert_eq!(interior_points(&[]), None);
+        assert_eq!(interior_points(&[Point::new(2, 3)]), None);
+        assert_eq!(interior_points(&[Point::new(0, 0), Point::new(0, 2)]), None);
+        let flat = [Point::new(0, 0), Point::new(3, 0), Point::new(5, 0)];
+        assert_eq!(interior_points(&flat), None);
+    }
+
+    #[test]
+    fn test_polygon_brute_force() {
+        // count lattice points strictly inside the triangle directly
+        let triangle = [Point::new(0, 0), Point::new(6, 0), Point::new(0, 4)];
+        let inside = (1..6)
+            .flat_map(|x| (1..4).map(move |y| (x, y)))
+            .filter(|&(x, y)| 4 * x + 6 * y < 24)
+            .count();
+        assert_eq!(interior_points(&triangle), Some(inside as u64));
+    }
+
+    #[test]
+    fn test_polygon_overflow() {
+        let huge = [
+            Point::new(i64::MIN, i64::MIN),
+            Point::new(i64::MAX, i64::MIN),
+            Point::new(i64::MAX, i64::MAX),
+            Point::new(i64::MIN, i64::MAX),
+        ];
+        assert_eq!(double_area(&huge), None);
+        assert_eq!(boundary_points(&huge), None);
+        assert_eq!(interior_points(&huge), None);
+    }
+}
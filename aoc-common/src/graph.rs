@@ -0,0 +1,478 @@
+//! Searches over graphs that are never built up front: each takes a start
+//! node and a closure giving a node's neighbours, so a grid, a map of names or
+//! a puzzle state can all be searched the same way. There's also cycle
+//! detection for iterated functions, and an [`Interner`] for handing out
+//! small ids in place of expensive keys.
+
+use std::borrow::Borrow;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// The number of steps from `start` to every node reachable from it,
+/// breadth first.
+pub fn bfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in neighbours(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// Every node reachable from `start`, in the order a depth first search
+/// reaches them. Neighbours are explored last first, so following a chain of
+/// nodes walks along it from one end.
+pub fn dfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut stack = vec![start];
+    let mut order = Vec::new();
+    while let Some(node) = stack.pop() {
+        if !seen.insert(node.clone()) {
+            continue;
+        }
+        stack.extend(neighbours(&node));
+        order.push(node);
+    }
+    order
+}
+
+/// The cheapest total cost from `start` to every node reachable from it,
+/// where `neighbours` gives each neighbour along with the cost of the step
+/// there. Paths whose cost overflows a `u64` are ignored.
+pub fn dijkstra<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> HashMap<N, u64>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    // the queue holds ids so nodes don't need to be ordered
+    let mut nodes = Interner::new();
+    let mut costs = HashMap::from([(start.clone(), 0_u64)]);
+    let mut queue = BinaryHeap::from([Reverse((0, nodes.intern(start)))]);
+    while let Some(Reverse((cost, id))) = queue.pop() {
+        let node = nodes.resolve(id).clone();
+        if cost > costs[&node] {
+            // already reached more cheaply
+            continue;
+        }
+
+        for (next, step) in neighbours(&node) {
+            let Some(next_cost) = cost.checked_add(step) else {
+                continue;
+            };
+            if costs.get(&next).is_none_or(|&known| next_cost < known) {
+                costs.insert(next.clone(), next_cost);
+                queue.push(Reverse((next_cost, nodes.intern(next))));
+            }
+        }
+    }
+    costs
+}
+
+/// The cheapest path from `start` to any node matching `is_goal`, along with
+/// its cost, or `None` if there isn't one. Searches like [`dijkstra`], but
+/// tries first the nodes that `heuristic` estimates are closest to a goal.
+///
+/// The answer is only guaranteed to be the cheapest when `heuristic` never
+/// overestimates; `|_| 0` always works, and turns this into plain Dijkstra.
+pub fn astar<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> u64,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(u64, Vec<N>)>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    let mut nodes = Interner::new();
+    let start_estimate = heuristic(&start);
+    let start = nodes.intern(start);
+    let mut costs = vec![0];
+    let mut previous = vec![None];
+
+    let mut queue = BinaryHeap::from([Reverse((start_estimate, 0, start))]);
+    while let Some(Reverse((_, cost, id))) = queue.pop() {
+        if cost > costs[id] {
+            continue;
+        }
+
+        let node = nodes.resolve(id).clone();
+        if is_goal(&node) {
+            let mut path = vec![node];
+            let mut current = id;
+            while let Some(before) = previous[current] {
+                path.push(nodes.resolve(before).clone());
+                current = before;
+            }
+            path.reverse();
+            return Some((cost, path));
+        }
+
+        for (next, step) in neighbours(&node) {
+            let Some(next_cost) = cost.checked_add(step) else {
+                continue;
+            };
+            let estimate = next_cost.saturating_add(heuristic(&next));
+            let next = nodes.intern(next);
+            if next == costs.len() {
+                costs.push(u64::MAX);
+                previous.push(None);
+            }
+            if next_cost < costs[next] {
+                costs[next] = next_cost;
+                previous[next] = Some(id);
+                queue.push(Reverse((estimate, next_cost, next)));
+            }
+        }
+    }
+    None
+}
+
+/// Where repeatedly applying a function starts going round in circles: after
+/// `start` steps the values repeat every `length` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+/// Finds the cycle in `start`, `f(start)`, `f(f(start))`, … with Floyd's
+/// tortoise and hare, holding only a couple of values at a time. Only returns
+/// once the sequence repeats, so `f` must have finitely many values.
+///
+/// See <https://en.wikipedia.org/wiki/Cycle_detection#Floyd's_tortoise_and_hare>.
+pub fn floyd<T: Clone + PartialEq>(start: T, mut f: impl FnMut(&T) -> T) -> Cycle {
+    // meet somewhere inside the cycle, with the hare going twice as fast
+    let mut tortoise = f(&start);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        let halfway = f(&hare);
+        hare = f(&halfway);
+    }
+
+    // the distance between them is now a multiple of the cycle length, so
+    // they meet again where the cycle begins
+    let mut cycle_start = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        cycle_start += 1;
+    }
+
+    let mut length = 1;
+    hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        length += 1;
+    }
+
+    Cycle {
+        start: cycle_start,
+        length,
+    }
+}
+
+/// Finds the same cycle as [`floyd`] using Brent's algorithm, which usually
+/// calls `f` fewer times.
+///
+/// See <https://en.wikipedia.org/wiki/Cycle_detection#Brent's_algorithm>.
+pub fn brent<T: Clone + PartialEq>(start: T, mut f: impl FnMut(&T) -> T) -> Cycle {
+    // the tortoise teleports to the hare at every power of two, so the hare
+    // catches it after exactly one lap once the power exceeds the length
+    let (mut power, mut length) = (1, 1);
+    let mut tortoise = start.clone();
+    let mut hare = f(&start);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = f(&hare);
+        length += 1;
+    }
+
+    // with the hare a whole lap ahead, they meet where the cycle begins
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..length {
+        hare = f(&hare);
+    }
+    let mut cycle_start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        cycle_start += 1;
+    }
+
+    Cycle {
+        start: cycle_start,
+        length,
+    }
+}
+
+/// Hands out a small id for each distinct value, counting up from zero in the
+/// order they're first seen, so they can index into `Vec`s instead of being
+/// hashed over and over.
+#[derive(Debug, Clone)]
+pub struct Interner<T> {
+    ids: HashMap<T, usize>,
+    values: Vec<T>,
+}
+
+impl<T> Default for Interner<T> {
+    fn default() -> Self {
+        Interner {
+            ids: HashMap::new(),
+            values: Vec::new(),
+        }
+    }
+}
+
+impl<T: Hash + Eq + Clone> Interner<T> {
+    pub fn new() -> Interner<T> {
+        Interner::default()
+    }
+
+    /// The id for `value`, giving it the next one if it hasn't been seen.
+    pub fn intern(&mut self, value: T) -> usize {
+        if let Some(&id) = self.ids.get(&value) {
+            return id;
+        }
+        let id = self.values.len();
+        self.ids.insert(value.clone(), id);
+        self.values.push(value);
+        id
+    }
+
+    /// The id already given to `value`, if any.
+    pub fn id<Q>(&self, value: &Q) -> Option<usize>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.ids.get(value).copied()
+    }
+
+    /// The value given the id `id`. Panics if no value has it.
+    pub fn resolve(&self, id: usize) -> &T {
+        &self.values[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Every value along with its id, in id order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.values.iter().enumerate()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Grid;
+
+    const MAZE: &str = "\
+.....#
+.###.#
+...#..
+##.#.#
+......
+";
+
+    fn maze() -> Grid<bool> {
+        Grid::parse(MAZE, |c| match c {
+            '.' => Ok(true),
+            '#' => Ok(false),
+            _ => Err("'.' or '#'"),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let maze = maze();
+        let open = |&p: &(usize, usize)| maze.neighbours4(p).filter(|&n| maze[n]);
+        let distances = bfs((0, 0), open);
+        assert_eq!(distances.len(), 20);
+        assert_eq!(distances[&(0, 0)], 0);
+        assert_eq!(distances[&(2, 2)], 4);
+        assert_eq!(distances[&(4, 5)], 9);
+        assert_eq!(distances[&(2, 5)], 7);
+    }
+
+    #[test]
+    fn test_dfs() {
+        // a loop of four, with a tail hanging off it
+        let edges: HashMap<u8, Vec<u8>> = HashMap::from([
+            (0, vec![1, 3]),
+            (1, vec![0, 2]),
+            (2, vec![1, 3, 4]),
+            (3, vec![2, 0]),
+            (4, vec![2]),
+            (5, vec![0]),
+        ]);
+        let order = dfs(0, |n| edges[n].clone());
+        assert_eq!(order, [0, 3, 2, 4, 1]);
+    }
+
+    type Edges = Vec<Vec<(usize, u64)>>;
+
+    // a small pseudorandom weighted digraph, and the cheapest costs between
+    // every pair of its nodes by Floyd–Warshall
+    fn weighted(seed: u64, size: usize) -> (Edges, Vec<Vec<Option<u64>>>) {
+        let mut state = seed;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        let mut edges = vec![Vec::new(); size];
+        for (from, edges) in edges.iter_mut().enumerate() {
+            for to in 0..size {
+                if from != to && next() % 3 == 0 {
+                    edges.push((to, next() % 10));
+                }
+            }
+        }
+
+        let mut costs = vec![vec![None; size]; size];
+        for (from, edges) in edges.iter().enumerate() {
+            costs[from][from] = Some(0);
+            for &(to, cost) in edges {
+                costs[from][to] = Some(costs[from][to].map_or(cost, |c: u64| c.min(cost)));
+            }
+        }
+        for via in 0..size {
+            for from in 0..size {
+                for to in 0..size {
+                    if let (Some(a), Some(b)) = (costs[from][via], costs[via][to]) {
+                        if costs[from][to].is_none_or(|c| a + b < c) {
+                            costs[from][to] = Some(a + b);
+                        }
+                    }
+                }
+            }
+        }
+        (edges, costs)
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar_brute_force() {
+        for seed in 1..=20 {
+            let (edges, expected) = weighted(seed, 8);
+            let neighbours = |&n: &usize| edges[n].clone();
+            for from in 0..8 {
+                let costs = dijkstra(from, neighbours);
+                for (to, expected) in expected[from].iter().enumerate() {
+                    assert_eq!(costs.get(&to).copied(), *expected, "{from} to {to}");
+
+                    let path = astar(from, neighbours, |_| 0, |&n| n == to);
+                    assert_eq!(path.as_ref().map(|(cost, _)| *cost), *expected);
+                    if let Some((cost, path)) = path {
+                        // the path is real and costs what it claims
+                        assert_eq!((path[0], path[path.len() - 1]), (from, to));
+                        let total: u64 = path
+                            .windows(2)
+                            .map(|step| {
+                                edges[step[0]]
+                                    .iter()
+                                    .filter(|&&(n, _)| n == step[1])
+                                    .map(|&(_, c)| c)
+                                    .min()
+                                    .unwrap()
+                            })
+                            .sum();
+                        assert_eq!(total, cost);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_astar_heuristic() {
+        let maze = maze();
+        let open = |&p: &(usize, usize)| maze.neighbours4(p).filter(|&n| maze[n]).map(|n| (n, 1));
+        let goal: (usize, usize) = (4, 5);
+        let (cost, path) = astar(
+            (0, 0),
+            open,
+            |&(row, column)| (goal.0.abs_diff(row) + goal.1.abs_diff(column)) as u64,
+            |&p| p == goal,
+        )
+        .unwrap();
+        assert_eq!(cost, 9);
+        assert_eq!(path.len(), 10);
+
+        assert_eq!(astar((0, 0), open, |_| 0, |&p| p == (0, 5)), None);
+    }
+
+    #[test]
+    fn test_cycles_brute_force() {
+        for modulus in 1..60_u64 {
+            for start in 0..modulus {
+                let f = |&x: &u64| (x * x + 1) % modulus;
+
+                let mut seen = HashMap::new();
+                let mut x = start;
+                let mut step = 0;
+                let expected = loop {
+                    if let Some(&first) = seen.get(&x) {
+                        break Cycle {
+                            start: first,
+                            length: step - first,
+                        };
+                    }
+                    seen.insert(x, step);
+                    x = f(&x);
+                    step += 1;
+                };
+
+                assert_eq!(floyd(start, f), expected, "{start} mod {modulus}");
+                assert_eq!(brent(start, f), expected, "{start} mod {modulus}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_interner() {
+        let mut names: Interner<String> = Interner::new();
+        assert!(names.is_empty());
+        assert_eq!(names.intern("AAA".into()), 0);
+        assert_eq!(names.intern("BBB".into()), 1);
+        assert_eq!(names.intern("AAA".into()), 0);
+        assert_eq!(names.len(), 2);
+        assert_eq!(names.id("BBB"), Some(1));
+        assert_eq!(names.id("CCC"), None);
+        assert_eq!(names.resolve(1), "BBB");
+        assert_eq!(
+            names.iter().collect::<Vec<_>>(),
+            [(0, &"AAA".to_string()), (1, &"BBB".to_string())]
+        );
+    }
+}
@@ -0,0 +1,296 @@
+use crate::ParseError;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A `(row, column)` position within a [`Grid`], counting from the top left.
+pub type Position = (usize, usize);
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Parses one row per line of `s`, turning each character into a cell with
+    /// `cell`. When `cell` rejects a character it returns what it `expected`
+    /// instead, which is reported at that character's position. Trailing
+    /// whitespace on each line is ignored, but every row must be as wide as the
+    /// first.
+    pub fn parse<E>(
+        s: &str,
+        mut cell: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Grid<T>, ParseError>
+    where
+        E: Into<String>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for line in s.lines() {
+            let line = line.trim_end();
+            let mut row_width = 0;
+            for (i, c) in line.char_indices() {
+                let fragment = &line[i..i + c.len_utf8()];
+                cells
+                    .push(cell(c).map_err(|expected| ParseError::expected(s, fragment, expected))?);
+                row_width += 1;
+            }
+
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(ParseError::expected(
+                        s,
+                        line,
+                        format!("a row of {width} cells"),
+                    ));
+                }
+                Some(_) => (),
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Grid {
+                width,
+                height,
+                cells,
+            }),
+            _ => Err(ParseError::unexpected_end(s, "a grid")),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, column): Position) -> bool {
+        row < self.height && column < self.width
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[position.0 * self.width + position.1])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if self.contains(position) {
+            Some(&mut self.cells[position.0 * self.width + position.1])
+        } else {
+            None
+        }
+    }
+
+    /// Every position in the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |column| (row, column)))
+    }
+
+    /// Every cell along with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The position of the first cell, row by row, that matches `predicate`.
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<Position> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        self.rows().nth(row)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |column| self.column(column))
+    }
+
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        let cells = if column < self.width {
+            &self.cells[column..]
+        } else {
+            &[]
+        };
+        cells.iter().step_by(self.width)
+    }
+
+    /// The positions above, below, left of and right of `position`, in that
+    /// order, leaving out any that fall off the grid.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        self.offsets(position, &[(-1, 0), (1, 0), (0, -1), (0, 1)])
+    }
+
+    /// The positions surrounding `position`, diagonals included, row by row,
+    /// leaving out any that fall off the grid.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        self.offsets(
+            position,
+            &[
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, -1),
+                (0, 1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+            ],
+        )
+    }
+
+    fn offsets(
+        &self,
+        (row, column): Position,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Position> + '_ {
+        offsets.iter().filter_map(move |&(dr, dc)| {
+            let position = (row.checked_add_signed(dr)?, column.checked_add_signed(dc)?);
+            self.contains(position).then_some(position)
+        })
+    }
+
+    /// A grid of the same shape with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        match self.get(position) {
+            Some(cell) => cell,
+            None => panic!(
+                "position {position:?} is outside a {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(position) {
+            Some(cell) => cell,
+            None => panic!("position {position:?} is outside a {width}x{height} grid"),
+        }
+    }
+}
+
+/// Writes one line per row, so a grid of cells that display as the character
+/// they were parsed from prints back out as its input.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TEST: &str = "\
+#.#
+..#
+";
+
+    fn parse(s: &str) -> Result<Grid<char>, ParseError> {
+        Grid::parse(s, |c| match c {
+            '#' | '.' => Ok(c),
+            _ => Err("'#' or '.'"),
+        })
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = parse(TEST).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 2)], '#');
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.to_string(), TEST);
+        assert_eq!(parse("#.#  \n..#\n").unwrap(), grid);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = parse("#.#\n.x#\n").unwrap_err();
+        assert_eq!(err.to_string(), "2:2: expected '#' or '.', found 'x'");
+
+        let err = parse("#.#\n.#\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "2:1: expected a row of 3 cells, found '.#'"
+        );
+
+        let err = parse("").unwrap_err();
+        assert_eq!(err.to_string(), "1:1: expected a grid, found end of input");
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = parse(TEST).unwrap();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            [['#', '.', '#'], ['.', '.', '#']]
+        );
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>(),
+            ["#.", "..", "##"]
+        );
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(grid.find(|&c| c == '.'), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = parse("...\n...\n...\n").unwrap();
+        assert_eq!(
+            grid.neighbours4((1, 1)).collect::<Vec<_>>(),
+            [(0, 1), (2, 1), (1, 0), (1, 2)]
+        );
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(
+            grid.neighbours8((2, 2)).collect::<Vec<_>>(),
+            [(1, 1), (1, 2), (2, 1)]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "position (0, 3) is outside a 3x2 grid")]
+    fn test_index_out_of_bounds() {
+        let grid = parse(TEST).unwrap();
+        let _ = grid[(0, 3)];
+    }
+}
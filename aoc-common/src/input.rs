@@ -0,0 +1,131 @@
+use std::fmt;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+/// Where to read a puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    Stdin,
+    File(PathBuf),
+}
+
+/// An input that couldn't be read, along with where we were looking.
+#[derive(Debug)]
+pub struct InputError {
+    input: Input,
+    source: io::Error,
+}
+
+impl Input {
+    /// Parses a command line argument, treating `-` as stdin.
+    pub fn from_arg(arg: &str) -> Input {
+        match arg {
+            "-" => Input::Stdin,
+            path => Input::File(path.into()),
+        }
+    }
+
+    pub fn read(&self) -> Result<String, InputError> {
+        let result = match self {
+            Input::Stdin => {
+                let mut buffer = String::new();
+                io::stdin().read_to_string(&mut buffer).map(|_| buffer)
+            }
+            Input::File(path) => std::fs::read_to_string(path),
+        };
+
+        result.map_err(|source| InputError {
+            input: self.clone(),
+            source,
+        })
+    }
+}
+
+/// Smooths over the ways a puzzle input changes as it's copied around: line
+/// endings become `\n`, trailing whitespace and blank lines at the end are
+/// dropped, and the last line always ends in a newline. Only trailing
+/// characters are removed, so positions within each line stay the same.
+pub fn normalise(input: &str) -> String {
+    let mut normalised: String = input
+        .lines()
+        .flat_map(|line| [line.trim_end(), "\n"])
+        .collect();
+    while normalised.ends_with("\n\n") || normalised == "\n" {
+        normalised.pop();
+    }
+    normalised
+}
+
+/// `example` mangled in each of the ways [`normalise`] undoes, for tests
+/// checking a solver gives the same answers for all of them.
+pub fn variants(example: &str) -> Vec<String> {
+    let unterminated = example.trim_end_matches('\n');
+    let trailing_spaces: String = example
+        .lines()
+        .map(|line| format!("{line}  \t\n"))
+        .collect();
+    vec![
+        example.replace('\n', "\r\n"),
+        trailing_spaces.clone(),
+        unterminated.into(),
+        format!("{unterminated}\n\n\n"),
+        trailing_spaces.trim_end_matches('\n').replace('\n', "\r\n"),
+    ]
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Input::Stdin => write!(f, "<stdin>"),
+            Input::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.source.kind() {
+            io::ErrorKind::NotFound => write!(f, "{}: no such input file", self.input),
+            _ => write!(f, "{}: couldn't read input: {}", self.input, self.source),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_arg() {
+        assert_eq!(Input::from_arg("-"), Input::Stdin);
+        assert_eq!(
+            Input::from_arg("day07/input"),
+            Input::File("day07/input".into())
+        );
+    }
+
+    #[test]
+    fn test_normalise() {
+        let example = "a b\n\nc\n";
+        assert_eq!(normalise(example), example);
+        for variant in variants(example) {
+            assert_eq!(normalise(&variant), example, "{variant:?}");
+        }
+
+        assert_eq!(normalise("  x  \r\n\r\n"), "  x\n");
+        assert_eq!(normalise(""), "");
+        assert_eq!(normalise("\n \n"), "");
+    }
+
+    #[test]
+    fn test_missing_file_names_path() {
+        let err = Input::from_arg("no/such/input").read().unwrap_err();
+        assert_eq!(err.to_string(), "no/such/input: no such input file");
+    }
+}
@@ -0,0 +1,517 @@
+//! Half-open ranges of integers, sets of them, and maps that shift different
+//! pieces of the number line by different offsets. Working on whole ranges at
+//! once keeps puzzles with billions of values down to a handful of intervals.
+
+use std::fmt;
+
+/// The integers from `start` up to but not including `end`. An interval whose
+/// `end` isn't past its `start` is empty. Since `end` is excluded, `i64::MAX`
+/// itself never falls in an interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    pub const fn new(start: i64, end: i64) -> Interval {
+        Interval { start, end }
+    }
+
+    /// The `len` integers counting up from `start`, or `None` if they run past
+    /// `i64::MAX`.
+    pub fn with_len(start: i64, len: u64) -> Option<Interval> {
+        Some(Interval::new(start, start.checked_add_unsigned(len)?))
+    }
+
+    pub fn len(self) -> u64 {
+        if self.is_empty() {
+            0
+        } else {
+            self.end.abs_diff(self.start)
+        }
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains(self, value: i64) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// The values in both intervals, or `None` if they don't overlap.
+    pub fn intersection(self, other: Interval) -> Option<Interval> {
+        let overlap = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!overlap.is_empty()).then_some(overlap)
+    }
+
+    /// The values below `at` and the values from `at` upwards, leaving out
+    /// either side if it's empty.
+    pub fn split_at(self, at: i64) -> (Option<Interval>, Option<Interval>) {
+        let below = Interval::new(self.start, self.end.min(at));
+        let above = Interval::new(self.start.max(at), self.end);
+        (
+            (!below.is_empty()).then_some(below),
+            (!above.is_empty()).then_some(above),
+        )
+    }
+
+    /// Every value moved by `offset`, or `None` if any would overflow.
+    pub fn checked_shift(self, offset: i64) -> Option<Interval> {
+        Some(Interval::new(
+            self.start.checked_add(offset)?,
+            self.end.checked_add(offset)?,
+        ))
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {})", self.start, self.end)
+    }
+}
+
+/// A set of integers, stored as the fewest intervals that cover it: sorted,
+/// non-empty, and with gaps between them.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> IntervalSet {
+        IntervalSet::default()
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        if !interval.is_empty() {
+            self.intervals.push(interval);
+            self.normalise();
+        }
+    }
+
+    // sort and merge overlapping or touching intervals, dropping empty ones
+    fn normalise(&mut self) {
+        self.intervals.retain(|interval| !interval.is_empty());
+        self.intervals.sort();
+
+        let mut merged: Vec<Interval> = Vec::with_capacity(self.intervals.len());
+        for interval in self.intervals.drain(..) {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+        self.intervals = merged;
+    }
+
+    /// The intervals making up the set, from lowest to highest.
+    pub fn iter(&self) -> impl Iterator<Item = Interval> + '_ {
+        self.intervals.iter().copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// How many values are in the set. This always fits, since the set can't
+    /// hold more than every `i64`.
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(|interval| interval.len()).sum()
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        // the first interval that ends after `value` is the only candidate
+        let i = self
+            .intervals
+            .partition_point(|interval| interval.end <= value);
+        self.intervals
+            .get(i)
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    pub fn max(&self) -> Option<i64> {
+        self.intervals.last().map(|interval| interval.end - 1)
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        self.iter().chain(other.iter()).collect()
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let (mut i, mut j) = (0, 0);
+        let mut intervals = Vec::new();
+        while let (Some(a), Some(b)) = (self.intervals.get(i), other.intervals.get(j)) {
+            intervals.extend(a.intersection(*b));
+            // whichever ends first can't overlap anything further along
+            if a.end <= b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        // pieces of separate intervals are still separate, so this is already
+        // normalised
+        IntervalSet { intervals }
+    }
+
+    /// The values in `self` that aren't in `other`.
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut removals = other.intervals.iter().peekable();
+        let mut intervals = Vec::new();
+        for interval in self.iter() {
+            while removals
+                .next_if(|removal| removal.end <= interval.start)
+                .is_some()
+            {}
+
+            // a removal can run on into the next interval, so look ahead
+            // without consuming
+            let mut start = interval.start;
+            let mut overlapping = removals.clone();
+            while let Some(removal) = overlapping.next_if(|removal| removal.start < interval.end) {
+                if removal.start > start {
+                    intervals.push(Interval::new(start, removal.start));
+                }
+                start = start.max(removal.end);
+            }
+            if start < interval.end {
+                intervals.push(Interval::new(start, interval.end));
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+
+    /// The values below `at` and the values from `at` upwards.
+    pub fn split_at(&self, at: i64) -> (IntervalSet, IntervalSet) {
+        let (mut below, mut above) = (IntervalSet::new(), IntervalSet::new());
+        for interval in self.iter() {
+            let (low, high) = interval.split_at(at);
+            below.intervals.extend(low);
+            above.intervals.extend(high);
+        }
+        (below, above)
+    }
+}
+
+impl From<Interval> for IntervalSet {
+    fn from(interval: Interval) -> IntervalSet {
+        IntervalSet::from_iter([interval])
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(intervals: I) -> IntervalSet {
+        let mut set = IntervalSet {
+            intervals: intervals.into_iter().collect(),
+        };
+        set.normalise();
+        set
+    }
+}
+
+impl fmt::Display for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        for (i, interval) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{interval}")?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// A map from integers to integers that moves each of a number of disjoint
+/// source intervals by its own offset, and leaves every other value where it
+/// is.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OffsetMap {
+    // sorted by source, never overlapping
+    pieces: Vec<(Interval, i64)>,
+}
+
+impl OffsetMap {
+    pub fn new() -> OffsetMap {
+        OffsetMap::default()
+    }
+
+    /// Moves the values in `source` by `offset`. Fails with the source of an
+    /// existing piece if the two overlap, since it'd be ambiguous which offset
+    /// applies.
+    pub fn insert(&mut self, source: Interval, offset: i64) -> Result<(), Interval> {
+        if source.is_empty() {
+            return Ok(());
+        }
+
+        let i = self
+            .pieces
+            .partition_point(|(existing, _)| existing.end <= source.start);
+        if let Some(&(existing, _)) = self.pieces.get(i) {
+            if existing.intersection(source).is_some() {
+                return Err(existing);
+            }
+        }
+        self.pieces.insert(i, (source, offset));
+        Ok(())
+    }
+
+    /// Moves the values in `source` that no existing piece moves yet by
+    /// `offset`, so where pieces overlap, the one inserted first wins.
+    pub fn insert_uncovered(&mut self, source: Interval, offset: i64) {
+        let sources: IntervalSet = self.pieces.iter().map(|&(source, _)| source).collect();
+        for uncovered in IntervalSet::from(source).difference(&sources).iter() {
+            self.insert(uncovered, offset)
+                .expect("the uncovered part of an interval overlaps nothing");
+        }
+    }
+
+    /// The source intervals and their offsets, from lowest to highest.
+    pub fn pieces(&self) -> impl Iterator<Item = (Interval, i64)> + '_ {
+        self.pieces.iter().copied()
+    }
+
+    /// Where `value` ends up, or `None` if moving it overflows.
+    pub fn map_value(&self, value: i64) -> Option<i64> {
+        let i = self
+            .pieces
+            .partition_point(|(source, _)| source.end <= value);
+        match self.pieces.get(i) {
+            Some(&(source, offset)) if source.contains(value) => value.checked_add(offset),
+            _ => Some(value),
+        }
+    }
+
+    /// Where every value in `set` ends up, or `None` if moving any of them
+    /// overflows.
+    pub fn map_set(&self, set: &IntervalSet) -> Option<IntervalSet> {
+        let mut mapped = Vec::new();
+        for &(source, offset) in &self.pieces {
+            for interval in set.intersection(&source.into()).iter() {
+                mapped.push(interval.checked_shift(offset)?);
+            }
+        }
+
+        let sources: IntervalSet = self.pieces.iter().map(|&(source, _)| source).collect();
+        mapped.extend(set.difference(&sources).iter());
+        Some(mapped.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // every subset of 0..12 made of at most three intervals, plus some edge
+    // cases, to compare against plain collections of values
+    fn sets() -> Vec<IntervalSet> {
+        let mut sets = vec![IntervalSet::new()];
+        for a in 0..12 {
+            for b in a + 1..=12 {
+                let first = Interval::new(a, b);
+                sets.push(first.into());
+                for c in b + 1..12 {
+                    sets.push(
+                        [first, Interval::new(c, (c + 2).min(12))]
+                            .into_iter()
+                            .collect(),
+                    );
+                    sets.push(
+                        [first, Interval::new(c, c + 1), Interval::new(11, 12)]
+                            .into_iter()
+                            .collect(),
+                    );
+                }
+            }
+        }
+        sets
+    }
+
+    fn values(set: &IntervalSet) -> Vec<i64> {
+        set.iter()
+            .flat_map(|interval| interval.start..interval.end)
+            .collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let interval = Interval::new(3, 7);
+        assert_eq!(interval.len(), 4);
+        assert!(interval.contains(3) && !interval.contains(7));
+        assert_eq!(Interval::new(5, 5).len(), 0);
+        assert_eq!(Interval::new(5, 2).len(), 0);
+        assert_eq!(Interval::new(i64::MIN, i64::MAX).len(), u64::MAX);
+
+        assert_eq!(
+            interval.intersection(Interval::new(5, 10)),
+            Some(Interval::new(5, 7))
+        );
+        assert_eq!(interval.intersection(Interval::new(7, 10)), None);
+
+        assert_eq!(
+            interval.split_at(5),
+            (Some(Interval::new(3, 5)), Some(Interval::new(5, 7)))
+        );
+        assert_eq!(interval.split_at(3), (None, Some(interval)));
+        assert_eq!(interval.split_at(9), (Some(interval), None));
+
+        assert_eq!(
+            Interval::with_len(i64::MAX - 2, 2),
+            Some(Interval::new(i64::MAX - 2, i64::MAX))
+        );
+        assert_eq!(Interval::with_len(i64::MAX - 2, 3), None);
+        assert_eq!(interval.checked_shift(-3), Some(Interval::new(0, 4)));
+        assert_eq!(interval.checked_shift(i64::MAX), None);
+        assert_eq!(interval.to_string(), "[3, 7)");
+    }
+
+    #[test]
+    fn test_normalise() {
+        let set: IntervalSet = [
+            Interval::new(8, 10),
+            Interval::new(0, 2),
+            Interval::new(2, 4),
+            Interval::new(6, 6),
+            Interval::new(9, 12),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            [Interval::new(0, 4), Interval::new(8, 12)]
+        );
+        assert_eq!(set.len(), 8);
+        assert_eq!((set.min(), set.max()), (Some(0), Some(11)));
+        assert_eq!(set.to_string(), "{[0, 4), [8, 12)}");
+
+        let mut set = set;
+        set.insert(Interval::new(3, 9));
+        assert_eq!(set, Interval::new(0, 12).into());
+    }
+
+    #[test]
+    fn test_set_operations_brute_force() {
+        let sets = sets();
+        for a in &sets {
+            let expected: Vec<i64> = (-1..13).filter(|&x| values(a).contains(&x)).collect();
+            assert_eq!(values(a), expected);
+            for x in -1..13 {
+                assert_eq!(a.contains(x), expected.contains(&x), "{x} in {a}");
+            }
+
+            let (below, above) = a.split_at(5);
+            assert_eq!(
+                values(&below),
+                expected
+                    .iter()
+                    .copied()
+                    .filter(|&x| x < 5)
+                    .collect::<Vec<_>>()
+            );
+            assert_eq!(
+                values(&above),
+                expected
+                    .iter()
+                    .copied()
+                    .filter(|&x| x >= 5)
+                    .collect::<Vec<_>>()
+            );
+
+            // only every seventh set on the other side, to keep this quick
+            for b in sets.iter().step_by(7) {
+                let (va, vb) = (values(a), values(b));
+                let union: Vec<i64> = (0..12)
+                    .filter(|x| va.contains(x) || vb.contains(x))
+                    .collect();
+                let intersection: Vec<i64> = (0..12)
+                    .filter(|x| va.contains(x) && vb.contains(x))
+                    .collect();
+                let difference: Vec<i64> = (0..12)
+                    .filter(|x| va.contains(x) && !vb.contains(x))
+                    .collect();
+
+                // comparing whole sets also checks each result is normalised
+                let from_values = |values: &[i64]| -> IntervalSet {
+                    values.iter().map(|&x| Interval::new(x, x + 1)).collect()
+                };
+                assert_eq!(a.union(b), from_values(&union), "{a} ∪ {b}");
+                assert_eq!(a.intersection(b), from_values(&intersection), "{a} ∩ {b}");
+                assert_eq!(a.difference(b), from_values(&difference), "{a} - {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_offset_map() {
+        let mut map = OffsetMap::new();
+        map.insert(Interval::new(98, 100), -48).unwrap();
+        map.insert(Interval::new(50, 98), 2).unwrap();
+        assert_eq!(
+            map.insert(Interval::new(90, 92), 1),
+            Err(Interval::new(50, 98))
+        );
+        assert_eq!(
+            map.insert(Interval::new(0, 51), 1),
+            Err(Interval::new(50, 98))
+        );
+        map.insert(Interval::new(10, 10), 1).unwrap();
+        assert_eq!(map.pieces().count(), 2);
+
+        assert_eq!(map.map_value(49), Some(49));
+        assert_eq!(map.map_value(50), Some(52));
+        assert_eq!(map.map_value(97), Some(99));
+        assert_eq!(map.map_value(98), Some(50));
+        assert_eq!(map.map_value(100), Some(100));
+
+        // every value in a set goes where it would on its own
+        for set in sets() {
+            let set: IntervalSet = set
+                .iter()
+                .map(|interval| Interval::new(interval.start * 9, interval.end * 9))
+                .collect();
+            let expected: IntervalSet = values(&set)
+                .into_iter()
+                .map(|x| {
+                    let y = map.map_value(x).unwrap();
+                    Interval::new(y, y + 1)
+                })
+                .collect();
+            assert_eq!(map.map_set(&set), Some(expected), "{set}");
+        }
+
+        let mut map = OffsetMap::new();
+        map.insert(Interval::new(0, 10), i64::MAX - 5).unwrap();
+        assert_eq!(map.map_value(5), Some(i64::MAX));
+        assert_eq!(map.map_value(6), None);
+        assert_eq!(
+            map.map_set(&Interval::new(0, 5).into()),
+            Some(Interval::new(i64::MAX - 5, i64::MAX).into())
+        );
+        // i64::MAX itself can't be held in a set
+        assert_eq!(map.map_set(&Interval::new(0, 6).into()), None);
+    }
+
+    #[test]
+    fn test_offset_map_uncovered() {
+        let mut map = OffsetMap::new();
+        map.insert_uncovered(Interval::new(10, 20), 1);
+        map.insert_uncovered(Interval::new(0, 30), 2);
+        map.insert_uncovered(Interval::new(15, 25), 3);
+        assert_eq!(
+            map.pieces().collect::<Vec<_>>(),
+            [
+                (Interval::new(0, 10), 2),
+                (Interval::new(10, 20), 1),
+                (Interval::new(20, 30), 2),
+            ]
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! Shared plumbing for the Advent of Code 2023 day crates.
+
+use std::str::FromStr;
+
+pub mod error;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod math;
+pub mod parser;
+pub mod random;
+pub mod solution;
+
+pub use error::{parse_number, ParseError, ParseErrorKind};
+pub use geometry::Point;
+pub use grid::{Grid, Position};
+pub use input::{Input, InputError};
+pub use interval::{Interval, IntervalSet, OffsetMap};
+pub use parser::Parser;
+pub use random::{differential, Rng};
+pub use solution::{Answer, Generated, PartRun, Run, Solution, Solver};
+
+/// The error type every solver bubbles up.
+pub type Error = Box<dyn std::error::Error>;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Parses every line of `s` into a `T`, stopping at the first failure and
+/// reporting it at its position within `s`.
+pub fn parse_lines<T>(s: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr<Err = ParseError>,
+{
+    s.lines()
+        .map(|line| line.parse().map_err(|err: ParseError| err.within(s, line)))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Number(u32);
+
+    impl FromStr for Number {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            parse_number(s, s.trim_start()).map(Number)
+        }
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        assert_eq!(
+            parse_lines::<Number>("1\n2\n3\n").unwrap(),
+            vec![Number(1), Number(2), Number(3)]
+        );
+
+        let err = parse_lines::<Number>("1\n  x\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+}
@@ -0,0 +1,227 @@
+//! Number theory that keeps turning up: gcd and lcm, the Chinese Remainder
+//! Theorem, integer square roots and binomial coefficients. Anything that can
+//! overflow returns `None` rather than wrapping or panicking.
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple of `a` and `b`, or `None` if it doesn't fit in a
+/// `u64`. The lcm of anything and zero is zero.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// The gcd of every number in `numbers`, which is zero if there are none.
+pub fn gcd_all(numbers: impl IntoIterator<Item = u64>) -> u64 {
+    numbers.into_iter().fold(0, gcd)
+}
+
+/// The lcm of every number in `numbers`, which is one if there are none, or
+/// `None` if it doesn't fit in a `u64`.
+pub fn lcm_all(numbers: impl IntoIterator<Item = u64>) -> Option<u64> {
+    numbers.into_iter().try_fold(1, lcm)
+}
+
+/// Solves `a·x + b·y = gcd(a, b)`, returning `(gcd, x, y)`.
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+/// The smallest `x` satisfying every congruence `x ≡ residue (mod modulus)`,
+/// along with the lcm of the moduli, which all solutions are spaced by.
+///
+/// The moduli don't need to be coprime. `None` if the congruences contradict
+/// each other, a modulus is zero, or the combined modulus overflows a `u64`.
+/// With no congruences at all, every `x` works, so that's `(0, 1)`.
+pub fn crt(congruences: impl IntoIterator<Item = (u64, u64)>) -> Option<(u64, u64)> {
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(r1, m1), (r2, m2)| {
+            if m2 == 0 {
+                return None;
+            }
+            let r2 = r2 % m2;
+
+            // x = r1 + m1·k, so we need m1·k ≡ r2 - r1 (mod m2)
+            let (g, inverse, _) = extended_gcd(m1 as i128, m2 as i128);
+            let difference = r2 as i128 - r1 as i128;
+            if difference % g != 0 {
+                return None;
+            }
+
+            let modulus = lcm(m1, m2)?;
+            let step = m2 as i128 / g;
+            // both factors are below 2^64, so their product fits in a u128
+            let k = (difference / g).rem_euclid(step) as u128 * inverse.rem_euclid(step) as u128
+                % step as u128;
+            let x = (r1 as u128 + m1 as u128 * k) % modulus as u128;
+            Some((x as u64, modulus))
+        })
+}
+
+/// The largest `r` with `r² <= n`.
+pub fn isqrt_u64(n: u64) -> u64 {
+    // a float gets within one of the answer; fix that up exactly
+    let mut r = (n as f64).sqrt() as u64;
+    while r.checked_mul(r).is_none_or(|square| square > n) {
+        r -= 1;
+    }
+    while (r + 1).checked_mul(r + 1).is_some_and(|square| square <= n) {
+        r += 1;
+    }
+    r
+}
+
+/// The largest `r` with `r² <= n`.
+pub fn isqrt_u128(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    // Newton's method, starting above the root so it descends onto it
+    let mut r = 1 << (u128::BITS - n.leading_zeros()).div_ceil(2);
+    loop {
+        let next = (r + n / r) / 2;
+        if next >= r {
+            return r;
+        }
+        r = next;
+    }
+}
+
+/// `n` choose `k`, or `None` if it doesn't fit in a `u64`.
+pub fn binomial(n: u64, k: u64) -> Option<u64> {
+    if k > n {
+        return Some(0);
+    }
+
+    // every partial product is itself a binomial no bigger than the answer,
+    // and each division is exact
+    let k = k.min(n - k);
+    (0..k).try_fold(1_u64, |acc, i| {
+        let next = acc as u128 * (n - i) as u128 / (i + 1) as u128;
+        next.try_into().ok()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        for a in 0..40_u64 {
+            for b in 0..40_u64 {
+                let divides_both = |d: &u64| a.is_multiple_of(*d) && b.is_multiple_of(*d);
+                let expected = (1..=a.max(b)).rev().find(divides_both).unwrap_or(0);
+                assert_eq!(gcd(a, b), expected, "gcd({a}, {b})");
+
+                let multiple_of_both = |m: &u64| m.is_multiple_of(a) && m.is_multiple_of(b);
+                let expected = if a == 0 || b == 0 {
+                    0
+                } else {
+                    (1..=a * b).find(multiple_of_both).unwrap()
+                };
+                assert_eq!(lcm(a, b), Some(expected), "lcm({a}, {b})");
+            }
+        }
+
+        assert_eq!(gcd_all([12, 18, 30]), 6);
+        assert_eq!(gcd_all([]), 0);
+        assert_eq!(lcm_all([2, 3, 4]), Some(12));
+        assert_eq!(lcm_all([]), Some(1));
+        assert_eq!(lcm_all([u64::MAX, u64::MAX - 1]), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        for m1 in 1..12 {
+            for m2 in 1..12 {
+                for r1 in 0..m1 {
+                    for r2 in 0..m2 {
+                        let modulus = lcm(m1, m2).unwrap();
+                        let expected = (0..modulus)
+                            .find(|x| x % m1 == r1 && x % m2 == r2)
+                            .map(|x| (x, modulus));
+                        assert_eq!(crt([(r1, m1), (r2, m2)]), expected);
+                    }
+                }
+            }
+        }
+
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(3, 4), (1, 6)]), Some((7, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([(1, 0)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+
+        // the largest primes below 2^32, whose product only just fits
+        let (p, q) = (4_294_967_291, 4_294_967_279);
+        assert_eq!(crt([(5, p), (5, q)]), Some((5, p * q)));
+        assert_eq!(
+            crt([(0, p), (1, q)]),
+            Some((1_537_228_665_292_936_540, p * q))
+        );
+        assert_eq!(crt([(0, u64::MAX), (0, u64::MAX - 1)]), None);
+    }
+
+    #[test]
+    fn test_isqrt() {
+        for n in 0..10_000_u64 {
+            let expected = (0..=n).take_while(|r| r * r <= n).last().unwrap();
+            assert_eq!(isqrt_u64(n), expected);
+            assert_eq!(isqrt_u128(n as u128), expected as u128);
+        }
+
+        for r in [u32::MAX as u64 - 1, u32::MAX as u64] {
+            assert_eq!(isqrt_u64(r * r), r);
+            assert_eq!(isqrt_u64(r * r - 1), r - 1);
+        }
+        assert_eq!(isqrt_u64(u64::MAX), u32::MAX as u64);
+
+        for r in [u64::MAX as u128 - 1, u64::MAX as u128] {
+            assert_eq!(isqrt_u128(r * r), r);
+            assert_eq!(isqrt_u128(r * r - 1), r - 1);
+        }
+        assert_eq!(isqrt_u128(u128::MAX), u64::MAX as u128);
+    }
+
+    #[test]
+    fn test_binomial() {
+        // check against Pascal's triangle, worked out wide enough to see
+        // exactly where the results stop fitting
+        let mut row: Vec<u128> = vec![1];
+        for n in 0..=70_u64 {
+            for (k, &expected) in row.iter().enumerate() {
+                assert_eq!(
+                    binomial(n, k as u64),
+                    u64::try_from(expected).ok(),
+                    "{n} choose {k}"
+                );
+            }
+            assert_eq!(binomial(n, n + 1), Some(0));
+
+            let next = std::iter::once(1)
+                .chain(row.windows(2).map(|pair| pair[0] + pair[1]))
+                .chain([1])
+                .collect();
+            row = next;
+        }
+
+        assert_eq!(binomial(u64::MAX, 1), Some(u64::MAX));
+        assert_eq!(binomial(u64::MAX, u64::MAX - 1), Some(u64::MAX));
+        assert_eq!(binomial(u64::MAX, 2), None);
+    }
+}
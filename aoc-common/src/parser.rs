@@ -0,0 +1,368 @@
+//! A small toolkit for picking puzzle inputs apart. A [`Parser`] works through
+//! its input from the front, and can split it into lines and blank-line
+//! separated blocks that are parsers in their own right. However finely the
+//! input gets split, errors point at the line and column within the whole
+//! input.
+
+use crate::{parse_number, ParseError};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Parser<'a> {
+    source: &'a str,
+    rest: &'a str,
+}
+
+fn starts_number(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '+'
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(source: &'a str) -> Parser<'a> {
+        Parser {
+            source,
+            rest: source,
+        }
+    }
+
+    // a parser over `fragment`, which must be part of the same source
+    fn over(&self, fragment: &'a str) -> Parser<'a> {
+        Parser {
+            source: self.source,
+            rest: fragment,
+        }
+    }
+
+    /// Everything not parsed yet.
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest.is_empty()
+    }
+
+    fn advance(&mut self, len: usize) -> &'a str {
+        let (taken, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        taken
+    }
+
+    fn current_line(&self) -> &'a str {
+        let line = self.rest.split('\n').next().unwrap_or_default();
+        line.strip_suffix('\r').unwrap_or(line)
+    }
+
+    /// `fragment` of the input isn't what we `expected`.
+    pub fn expected(&self, fragment: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::expected(self.source, fragment, expected)
+    }
+
+    /// `fragment` of the input is well formed but doesn't make sense.
+    pub fn invalid(&self, fragment: &str, reason: impl Into<String>) -> ParseError {
+        ParseError::invalid(self.source, fragment, reason)
+    }
+
+    /// We `expected` something other than what comes next, which is reported
+    /// as the rest of the current line.
+    pub fn expected_here(&self, expected: impl Into<String>) -> ParseError {
+        let found = self
+            .current_line()
+            .trim_start_matches([' ', '\t'])
+            .trim_end();
+        if found.is_empty() {
+            ParseError::unexpected_end(found, expected).within(self.source, found)
+        } else {
+            self.expected(found, expected)
+        }
+    }
+
+    /// Skips over `literal`, which must come next.
+    pub fn literal(&mut self, literal: &str) -> Result<(), ParseError> {
+        if self.eat(literal) {
+            Ok(())
+        } else {
+            Err(self.expected_here(format!("'{literal}'")))
+        }
+    }
+
+    /// Skips over `literal` if it comes next, returning whether it did.
+    pub fn eat(&mut self, literal: &str) -> bool {
+        match self.rest.strip_prefix(literal) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Skips spaces and tabs, but not line breaks.
+    pub fn spaces(&mut self) {
+        self.rest = self.rest.trim_start_matches([' ', '\t']);
+    }
+
+    /// A run of letters and digits.
+    pub fn word(&mut self) -> Result<&'a str, ParseError> {
+        let len = self
+            .rest
+            .find(|c: char| !c.is_alphanumeric())
+            .unwrap_or(self.rest.len());
+        if len == 0 {
+            return Err(self.expected_here("a word"));
+        }
+        Ok(self.advance(len))
+    }
+
+    /// A [`Parser::word`] parsed as a `T`, with any error placed within the
+    /// whole input.
+    pub fn parse_word<T>(&mut self) -> Result<T, ParseError>
+    where
+        T: FromStr<Err = ParseError>,
+    {
+        let word = self.word()?;
+        word.parse()
+            .map_err(|err: ParseError| err.within(self.source, word))
+    }
+
+    /// An integer, optionally signed. Letters straight after the digits are
+    /// taken as part of it, so `12ab` is an invalid number rather than `12`
+    /// followed by `ab`.
+    pub fn number<T>(&mut self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let sign = usize::from(self.rest.starts_with(['-', '+']));
+        let len = self.rest[sign..]
+            .find(|c: char| !c.is_alphanumeric())
+            .map_or(self.rest.len(), |len| sign + len);
+        if len == sign {
+            return Err(self.expected_here("a number"));
+        }
+        parse_number(self.source, self.advance(len))
+    }
+
+    /// Numbers separated by spaces, up to the first thing that can't start
+    /// one, such as a line break. There may be none at all.
+    pub fn numbers<T>(&mut self) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let mut numbers = Vec::new();
+        loop {
+            self.spaces();
+            if !self.rest.starts_with(starts_number) {
+                return Ok(numbers);
+            }
+            numbers.push(self.number()?);
+        }
+    }
+
+    /// One or more items parsed by `item`, with `separator` between each.
+    pub fn separated<T>(
+        &mut self,
+        separator: &str,
+        mut item: impl FnMut(&mut Parser<'a>) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut items = vec![item(self)?];
+        while self.eat(separator) {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    /// Everything up to `delimiter` on the current line, skipping over the
+    /// delimiter too.
+    pub fn until(&mut self, delimiter: &str) -> Result<&'a str, ParseError> {
+        let line = self.current_line();
+        match line.find(delimiter) {
+            Some(len) => {
+                let taken = self.advance(len);
+                self.advance(delimiter.len());
+                Ok(taken)
+            }
+            None => {
+                let end = &line[line.len()..];
+                let expected = format!("'{delimiter}'");
+                Err(ParseError::unexpected_end(end, expected).within(self.source, end))
+            }
+        }
+    }
+
+    /// Skips over `label` and a colon, then any spaces before what it labels.
+    pub fn label(&mut self, label: &str) -> Result<(), ParseError> {
+        self.literal(&format!("{label}:"))?;
+        self.spaces();
+        Ok(())
+    }
+
+    /// Skips over the end of the current line, which must be nothing but
+    /// spaces.
+    pub fn end_of_line(&mut self) -> Result<(), ParseError> {
+        self.spaces();
+        if self.rest.is_empty() || self.eat("\n") || self.eat("\r\n") {
+            Ok(())
+        } else {
+            Err(self.expected_here("end of line"))
+        }
+    }
+
+    /// Checks there's nothing left but whitespace.
+    pub fn end(&mut self) -> Result<(), ParseError> {
+        self.rest = self.rest.trim_start();
+        if self.rest.is_empty() {
+            Ok(())
+        } else {
+            Err(self.expected_here("end of input"))
+        }
+    }
+
+    /// Splits off every remaining line as a parser of its own.
+    pub fn lines(&mut self) -> impl Iterator<Item = Parser<'a>> {
+        let parser = *self;
+        self.advance(self.rest.len());
+        parser.rest.lines().map(move |line| parser.over(line))
+    }
+
+    /// Parses every remaining line with `line`, which must use up the whole
+    /// line.
+    pub fn each_line<T>(
+        &mut self,
+        mut line: impl FnMut(&mut Parser<'a>) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        self.lines()
+            .map(|mut parser| {
+                let value = line(&mut parser)?;
+                parser.end_of_line()?;
+                Ok(value)
+            })
+            .collect()
+    }
+
+    /// Splits off every remaining block of lines as a parser of its own,
+    /// where blocks are separated by one or more blank lines.
+    pub fn blocks(&mut self) -> impl Iterator<Item = Parser<'a>> {
+        let parser = *self;
+        let rest = self.advance(self.rest.len());
+        let offset = move |line: &str| line.as_ptr() as usize - rest.as_ptr() as usize;
+
+        let mut lines = rest.lines().peekable();
+        std::iter::from_fn(move || {
+            while lines.next_if(|line| line.trim().is_empty()).is_some() {}
+            let first = lines.next()?;
+            let mut last = first;
+            while let Some(line) = lines.next_if(|line| !line.trim().is_empty()) {
+                last = line;
+            }
+            Some(parser.over(&rest[offset(first)..offset(last) + last.len()]))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tokens() {
+        let mut parser = Parser::new("Card  12: 41 -48 +6 | x");
+        parser.literal("Card").unwrap();
+        parser.spaces();
+        assert_eq!(parser.number::<u32>().unwrap(), 12);
+        parser.label("").unwrap();
+        assert_eq!(parser.numbers::<i32>().unwrap(), [41, -48, 6]);
+        assert!(!parser.eat("x"));
+        assert!(parser.eat("| "));
+        assert_eq!(parser.word().unwrap(), "x");
+        parser.end().unwrap();
+    }
+
+    #[test]
+    fn test_separated() {
+        let mut parser = Parser::new("Game 1: 3 blue, 4 red; 1 red");
+        assert_eq!(parser.until(":").unwrap(), "Game 1");
+        let draws = parser
+            .separated(";", |draw| {
+                draw.separated(",", |cubes| {
+                    cubes.spaces();
+                    let count: u32 = cubes.number()?;
+                    cubes.literal(" ")?;
+                    Ok((count, cubes.word()?))
+                })
+            })
+            .unwrap();
+        assert_eq!(draws, [vec![(3, "blue"), (4, "red")], vec![(1, "red")]]);
+    }
+
+    #[test]
+    fn test_lines_and_blocks() {
+        let input = "seeds: 1 2\n\nfirst:\n1 2\n3 4\n\n\nsecond:\n5 6\n";
+        let mut parser = Parser::new(input);
+        let blocks: Vec<_> = parser.blocks().map(|block| block.rest()).collect();
+        assert_eq!(blocks, ["seeds: 1 2", "first:\n1 2\n3 4", "second:\n5 6"]);
+        assert!(parser.is_empty());
+
+        let mut blocks = Parser::new(input).blocks();
+        let mut seeds = blocks.next().unwrap();
+        seeds.label("seeds").unwrap();
+        assert_eq!(seeds.numbers::<u32>().unwrap(), [1, 2]);
+
+        let mut first = blocks.next().unwrap();
+        assert_eq!(first.until(":").unwrap(), "first");
+        first.end_of_line().unwrap();
+        let rows = first.each_line(|line| line.numbers::<u32>()).unwrap();
+        assert_eq!(rows, [[1, 2], [3, 4]]);
+
+        // positions are still counted from the start of the whole input
+        let mut second = blocks.next().unwrap();
+        let err = second.each_line(|line| line.word()).unwrap_err();
+        assert_eq!(err.to_string(), "8:7: expected end of line, found ':'");
+
+        let lines = Parser::new("a\r\nb\n").lines().count();
+        assert_eq!(lines, 2);
+    }
+
+    #[test]
+    fn test_errors() {
+        let input = "Time: 7 15\nDistance 9 4x\n";
+        let mut parser = Parser::new(input);
+        parser.label("Time").unwrap();
+        parser.numbers::<u32>().unwrap();
+        parser.end_of_line().unwrap();
+
+        let err = parser.label("Distance").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "2:1: expected 'Distance:', found 'Distance 9 4x'"
+        );
+
+        parser.literal("Distance").unwrap();
+        let err = parser.numbers::<u32>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "2:12: invalid number '4x': invalid digit found in string"
+        );
+
+        let err = Parser::new("Game 1 3 blue").until(":").unwrap_err();
+        assert_eq!(err.to_string(), "1:14: expected ':', found end of input");
+
+        let err = Parser::new("ab  ").number::<u8>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "1:1: invalid number 'ab': invalid digit found in string"
+        );
+
+        let mut parser = Parser::new("12  \n");
+        parser.number::<u8>().unwrap();
+        let err = parser.literal(",").unwrap_err();
+        assert_eq!(err.to_string(), "1:5: expected ',', found end of input");
+        let err = parser.word().unwrap_err();
+        assert_eq!(err.to_string(), "1:5: expected a word, found end of input");
+
+        let err = Parser::new("1\n2\n").end().unwrap_err();
+        assert_eq!(err.to_string(), "1:1: expected end of input, found '1'");
+    }
+}
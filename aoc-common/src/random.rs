@@ -0,0 +1,111 @@
+//! A small seeded random number generator for generating puzzle inputs, so a
+//! seed gives the same input on every run and every platform.
+
+use std::fmt::Debug;
+use std::ops::Range;
+
+/// A xorshift generator. It's nowhere near good enough for anything that
+/// matters, but puzzle inputs only need to look varied.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    /// Starts a generator from `seed`. Nearby seeds are scrambled with a
+    /// round of splitmix64 so they don't start out alike, and so a seed of
+    /// zero, which xorshift would never leave, works like any other.
+    pub fn new(seed: u64) -> Rng {
+        let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        Rng((z ^ (z >> 31)).max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number in `0..bound`, which mustn't be empty.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "can't pick a number below 0");
+        self.next_u64() % bound
+    }
+
+    /// A number in `range`, which mustn't be empty.
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        range.start + self.below(range.end - range.start)
+    }
+
+    /// True one time in `n`.
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.below(n) == 0
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+/// Checks that `fast` and `slow` agree on `cases` inputs made by `generate`,
+/// for testing a solver against a simpler one that's too slow for real
+/// inputs. Each case gets a generator seeded with its number, so a failing
+/// case can be made again on its own.
+pub fn differential<T: Debug, R: PartialEq + Debug>(
+    cases: u64,
+    mut generate: impl FnMut(&mut Rng) -> T,
+    fast: impl Fn(&T) -> R,
+    slow: impl Fn(&T) -> R,
+) {
+    for case in 0..cases {
+        let input = generate(&mut Rng::new(case));
+        assert_eq!(fast(&input), slow(&input), "case {case}: {input:?}");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_repeatable() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.below(1000)).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(7), numbers(7));
+        assert_ne!(numbers(7), numbers(8));
+        assert_ne!(numbers(0), [0; 5]);
+    }
+
+    #[test]
+    fn test_range() {
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            assert!((10..13).contains(&rng.range(10..13)));
+        }
+
+        let mut items = [1, 2, 3, 4, 5];
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_differential() {
+        differential(100, |rng| rng.below(1000), |&n| n * 2, |&n| n + n);
+    }
+
+    #[test]
+    #[should_panic(expected = "case 0: 7")]
+    fn test_differential_disagreement() {
+        differential(10, |_| 7, |&n| n * n, |&n| n + n);
+    }
+}
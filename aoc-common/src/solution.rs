@@ -0,0 +1,259 @@
+use crate::random::Rng;
+use crate::{input, ParseError, Result};
+use std::fmt;
+use std::time::{Duration, Instant};
+use tracing::info_span;
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Text(String),
+}
+
+macro_rules! answer_from {
+    ($variant:ident as $target:ty: $($source:ty),*) => {
+        $(
+            impl From<$source> for Answer {
+                fn from(value: $source) -> Self {
+                    Answer::$variant(value as $target)
+                }
+            }
+        )*
+    };
+}
+
+answer_from!(Unsigned as u64: u32, u64, usize);
+answer_from!(Signed as i64: i32, i64, isize);
+
+/// Lets tests compare answers against plain integers, whichever width the
+/// day happened to use.
+macro_rules! answer_eq {
+    ($($other:ty),*) => {
+        $(
+            impl PartialEq<$other> for Answer {
+                fn eq(&self, other: &$other) -> bool {
+                    match self {
+                        Answer::Unsigned(value) => *value as i128 == *other as i128,
+                        Answer::Signed(value) => *value as i128 == *other as i128,
+                        Answer::Text(_) => false,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+answer_eq!(u32, u64, usize, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.into())
+    }
+}
+
+impl Answer {
+    /// What sort of value the answer is, for machine-readable output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Unsigned(_) => "unsigned",
+            Answer::Signed(_) => "signed",
+            Answer::Text(_) => "text",
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Unsigned(value) => write!(f, "{value}"),
+            Answer::Signed(value) => write!(f, "{value}"),
+            Answer::Text(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// A randomly generated puzzle input, along with the answers the generator
+/// could work out for itself without going through the solver.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    pub input: String,
+    pub answers: Vec<(u8, Answer)>,
+}
+
+/// One day's puzzle: a parse shared by both parts, then the parts themselves.
+pub trait Solution {
+    const DAY: u8;
+
+    type Parsed;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError>;
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer>;
+
+    fn part2(parsed: &Self::Parsed) -> Result<Answer>;
+
+    /// Generates a valid input whose scale grows with `size`, for the days
+    /// that know how. What `size` counts is up to each day.
+    fn generate(_rng: &mut Rng, _size: usize) -> Option<Generated> {
+        None
+    }
+}
+
+/// One part's answer, or why there isn't one, and how long it took.
+#[derive(Debug)]
+pub struct PartRun {
+    pub part: u8,
+    pub answer: Result<Answer>,
+    pub solve_time: Duration,
+}
+
+/// A timed run of a solver over one input. If parsing fails there are no
+/// parts to report.
+#[derive(Debug)]
+pub struct Run {
+    pub parse_time: Duration,
+    pub parts: Result<Vec<PartRun>, ParseError>,
+}
+
+/// An object-safe view of a [`Solution`], so days can sit side by side in a
+/// table and be shared between threads. Implemented for every `Solution`.
+pub trait Solver: Sync {
+    fn day(&self) -> u8;
+
+    /// Parses `input` once and solves each of the requested `parts`, timing
+    /// each step. The input is [normalised](crate::input::normalise) first.
+    /// The steps run in `parse`, `part1` and `part2` tracing spans.
+    fn run(&self, input: &str, parts: &[u8]) -> Run;
+
+    /// Generates an input from `seed`, if the day has a generator. The same
+    /// seed and size always give the same input.
+    fn generate(&self, seed: u64, size: usize) -> Option<Generated>;
+
+    /// Parses `input` once and solves each of the requested `parts`, stopping
+    /// at the first failure.
+    fn solve(&self, input: &str, parts: &[u8]) -> Result<Vec<Answer>> {
+        self.run(input, parts)
+            .parts?
+            .into_iter()
+            .map(|part| part.answer)
+            .collect()
+    }
+}
+
+impl<S: Solution + Sync> Solver for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<Generated> {
+        S::generate(&mut Rng::new(seed), size)
+    }
+
+    fn run(&self, input: &str, parts: &[u8]) -> Run {
+        let input = input::normalise(input);
+        let start = Instant::now();
+        let parsed = info_span!("parse", day = S::DAY).in_scope(|| S::parse(&input));
+        let parse_time = start.elapsed();
+
+        let parts = parsed.map(|parsed| {
+            parts
+                .iter()
+                .map(|&part| {
+                    let start = Instant::now();
+                    let answer = match part {
+                        1 => info_span!("part1", day = S::DAY).in_scope(|| S::part1(&parsed)),
+                        2 => info_span!("part2", day = S::DAY).in_scope(|| S::part2(&parsed)),
+                        _ => Err(format!("there is no part {part}").into()),
+                    };
+                    let solve_time = start.elapsed();
+
+                    PartRun {
+                        part,
+                        answer,
+                        solve_time,
+                    }
+                })
+                .collect()
+        });
+
+        Run { parse_time, parts }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 0;
+
+        type Parsed = Vec<u32>;
+
+        fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+            input
+                .lines()
+                .map(|line| crate::parse_number(input, line))
+                .collect()
+        }
+
+        fn part1(parsed: &Self::Parsed) -> Result<Answer> {
+            Ok(parsed.iter().sum::<u32>().into())
+        }
+
+        fn part2(parsed: &Self::Parsed) -> Result<Answer> {
+            Ok(parsed.iter().map(|&n| -(n as i64)).sum::<i64>().into())
+        }
+    }
+
+    #[test]
+    fn test_solver() {
+        let solver: &dyn Solver = &Sum;
+        assert_eq!(solver.day(), 0);
+        assert_eq!(
+            solver.solve("1\n2\n3\n", &[2, 1]).unwrap(),
+            [Answer::Signed(-6), Answer::Unsigned(6)]
+        );
+        assert!(solver.solve("1\nx\n", &[1]).is_err());
+        assert!(solver.solve("1\n", &[3]).is_err());
+    }
+
+    #[test]
+    fn test_run() {
+        let run = Sum.run("1\n2\n", &[1, 3]);
+        let parts = run.parts.unwrap();
+        assert_eq!(parts[0].part, 1);
+        assert_eq!(parts[0].answer.as_ref().unwrap(), &Answer::Unsigned(3));
+        assert_eq!(
+            parts[1].answer.as_ref().unwrap_err().to_string(),
+            "there is no part 3"
+        );
+
+        let err = Sum.run("1\nx\n", &[1, 2]).parts.unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!(Answer::from(5905_u32).to_string(), "5905");
+        assert_eq!(Answer::from(-2_i32).to_string(), "-2");
+        assert_eq!(Answer::from("LRL").to_string(), "LRL");
+    }
+
+    #[test]
+    fn test_answer_eq() {
+        assert_eq!(Answer::from(30_usize), 30);
+        assert_eq!(Answer::from(-2_i64), -2);
+        assert_ne!(Answer::from(u64::MAX), -1_i64);
+        assert_ne!(Answer::from("30"), 30);
+    }
+}
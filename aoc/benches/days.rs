@@ -0,0 +1,74 @@
+//! Times parsing and each part separately, on the real inputs where they're
+//! present and on generated inputs scaled up for the days that brute force
+//! their way to an answer.
+//!
+//! Run with `cargo bench -p aoc`, or e.g. `cargo bench -p aoc -- day05` for a
+//! single day.
+
+use aoc_common::{input, Rng, Solution};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::path::Path;
+
+/// Benchmarks parse, part1 and part2 of `S` against `input`, labelled `id`.
+fn bench_solution<S: Solution>(c: &mut Criterion, group: &str, id: impl ToString, input: &str) {
+    let id = id.to_string();
+    let parsed = match S::parse(input) {
+        Ok(parsed) => parsed,
+        Err(err) => panic!("day {} {group} {id}: {err}", S::DAY),
+    };
+
+    let mut group = c.benchmark_group(format!("day{:02}/{group}", S::DAY));
+    group.sample_size(10);
+    group.bench_with_input(BenchmarkId::new("parse", &id), input, |b, input| {
+        b.iter(|| S::parse(black_box(input)))
+    });
+    group.bench_with_input(BenchmarkId::new("part1", &id), &parsed, |b, parsed| {
+        b.iter(|| S::part1(black_box(parsed)))
+    });
+    group.bench_with_input(BenchmarkId::new("part2", &id), &parsed, |b, parsed| {
+        b.iter(|| S::part2(black_box(parsed)))
+    });
+    group.finish();
+}
+
+/// Benchmarks `S` on its `dayNN/input`, if there is one.
+fn bench_input<S: Solution>(c: &mut Criterion) {
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let path = workspace.join(format!("day{:02}", S::DAY)).join("input");
+    if let Ok(input) = std::fs::read_to_string(path) {
+        bench_solution::<S>(c, "input", "real", &input::normalise(&input));
+    }
+}
+
+fn inputs(c: &mut Criterion) {
+    bench_input::<day1::Day1>(c);
+    bench_input::<day2::Day2>(c);
+    bench_input::<day3::Day3>(c);
+    bench_input::<day4::Day4>(c);
+    bench_input::<day5::Day5>(c);
+    bench_input::<day6::Day6>(c);
+    bench_input::<day7::Day7>(c);
+    bench_input::<day8::Day8>(c);
+    bench_input::<day9::Day9>(c);
+    bench_input::<day10::Day10>(c);
+    bench_input::<day11::Day11>(c);
+}
+
+/// Benchmarks `S` on inputs from its generator at each of `sizes`.
+fn bench_generated<S: Solution>(c: &mut Criterion, sizes: [usize; 3]) {
+    for size in sizes {
+        let generated = S::generate(&mut Rng::new(1), size)
+            .unwrap_or_else(|| panic!("day {} has no input generator", S::DAY));
+        bench_solution::<S>(c, "scaled", size, &generated.input);
+    }
+}
+
+fn scaled(c: &mut Criterion) {
+    bench_generated::<day4::Day4>(c, [100, 200, 400]);
+    bench_generated::<day5::Day5>(c, [10_000, 100_000, 1_000_000]);
+    bench_generated::<day6::Day6>(c, [10_000, 100_000, 1_000_000]);
+    bench_generated::<day11::Day11>(c, [40, 80, 160]);
+}
+
+criterion_group!(benches, inputs, scaled);
+criterion_main!(benches);
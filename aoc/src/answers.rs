@@ -0,0 +1,149 @@
+use crate::days;
+use aoc_common::{parse_number, ParseError, Result};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+const HEADER: &str = "# day part input-hash answer\n";
+
+/// Hashes a puzzle input so recorded answers stay tied to the input they were
+/// computed from. This is 64-bit FNV-1a, which is stable across builds and
+/// platforms, unlike `std`'s hashers.
+pub fn input_hash(input: &str) -> u64 {
+    input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// The `answers` file in the workspace root.
+pub fn default_path() -> PathBuf {
+    days::workspace().join("answers")
+}
+
+/// Answers we've earned stars for, keyed by day, part and input hash.
+///
+/// The file has one answer per line, as `<day> <part> <hash> <answer>`, with
+/// blank lines and `#` comments ignored.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    entries: BTreeMap<(u8, u8, u64), String>,
+}
+
+impl FromStr for Answers {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut entries = BTreeMap::new();
+        for line in s.lines() {
+            let content = line.split('#').next().unwrap().trim();
+            if content.is_empty() {
+                continue;
+            }
+
+            let fields: Vec<&str> = content.split_whitespace().collect();
+            let &[day, part, hash, answer] = fields.as_slice() else {
+                return Err(ParseError::expected(
+                    s,
+                    content,
+                    "'<day> <part> <input-hash> <answer>'",
+                ));
+            };
+            let day = parse_number(s, day)?;
+            let part = parse_number(s, part)?;
+            let hash = u64::from_str_radix(hash, 16)
+                .map_err(|_| ParseError::expected(s, hash, "a hexadecimal input hash"))?;
+
+            if entries.insert((day, part, hash), answer.into()).is_some() {
+                return Err(ParseError::invalid(s, content, "duplicate answer"));
+            }
+        }
+
+        Ok(Answers { entries })
+    }
+}
+
+impl Answers {
+    /// Reads the answers at `path`, treating a missing file as empty.
+    pub fn load(path: &Path) -> Result<Answers> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(contents
+                .parse()
+                .map_err(|err| format!("{}:{err}", path.display()))?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(err) => Err(format!("{}: couldn't read answers: {err}", path.display()).into()),
+        }
+    }
+
+    pub fn get(&self, day: u8, part: u8, hash: u64) -> Option<&str> {
+        self.entries.get(&(day, part, hash)).map(String::as_str)
+    }
+
+    /// Records `answer`, appending it to the file at `path` so hand-written
+    /// comments survive.
+    pub fn record(
+        &mut self,
+        path: &Path,
+        day: u8,
+        part: u8,
+        hash: u64,
+        answer: &str,
+    ) -> Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        if file.metadata()?.len() == 0 {
+            file.write_all(HEADER.as_bytes())?;
+        }
+        writeln!(file, "{day} {part} {hash:016x} {answer}")?;
+
+        self.entries.insert((day, part, hash), answer.into());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_input_hash() {
+        assert_eq!(input_hash(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(input_hash("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(input_hash("32T3K 765\n"), input_hash("32T3K 765"));
+    }
+
+    #[test]
+    fn test_parse() {
+        let answers: Answers = "\
+# day part input-hash answer
+7 1 00000000deadbeef 6440
+
+7 2 00000000deadbeef 5905  # with jokers
+"
+        .parse()
+        .unwrap();
+        assert_eq!(answers.get(7, 1, 0xdeadbeef), Some("6440"));
+        assert_eq!(answers.get(7, 2, 0xdeadbeef), Some("5905"));
+        assert_eq!(answers.get(7, 2, 0xfeedface), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = "7 1 6440\n".parse::<Answers>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "1:1: expected '<day> <part> <input-hash> <answer>', found '7 1 6440'"
+        );
+
+        let err = "7 1 ff 6440\n7 2 xyz 5905\n"
+            .parse::<Answers>()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "2:5: expected a hexadecimal input hash, found 'xyz'"
+        );
+
+        let err = "7 1 ff 6440\n7 1 ff 6441\n".parse::<Answers>().unwrap_err();
+        assert_eq!(err.to_string(), "2:1: duplicate answer: '7 1 ff 6441'");
+    }
+}
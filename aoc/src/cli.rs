@@ -0,0 +1,526 @@
+use aoc_common::{Input, Result};
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+pub const USAGE: &str = "\
+usage: aoc run <day> [--part 1|2] [--input <path>|-] [--format text|json] [-v|--explain]
+       aoc run --all [--format text|json] [--jobs <n>] [--budget <ms>]
+       aoc verify [<day>] [--input <path>|-] [--answers <path>] [--record]
+       aoc fetch <day>
+       aoc submit <day> <part>
+       aoc new <day>
+       aoc watch <day>
+       aoc gen <day> [--seed <n>] [--size <n>] [--answers <path>]
+
+Inputs default to dayNN/input in the workspace; pass --input - to read stdin.
+--format json prints one JSON record per line for each day and part.
+-v traces parsing and each part to stderr with their timings, -vv adds what
+the solvers find along the way, and -vvv or --explain adds every step.
+--all solves up to --jobs days at once (default one per CPU), then prints how
+long each part took, flagging any over --budget milliseconds (default 1000).
+verify checks answers against those recorded in the workspace's answers file,
+and --record adds any that aren't recorded yet.
+fetch downloads a day's input into the cache, then copies it to dayNN/input if
+that's missing. The session token comes from AOC_SESSION or the config file,
+AOC_CONFIG or else ~/.config/aoc/config, which can also set base-url, year and
+cache.
+submit solves a part of a day's input and sends the answer, keeping a history
+of attempts next to the cached inputs. It won't send an answer the history
+says is wrong, too high or too low, or before the site's wait is up.
+new creates a dayNN crate with a stub solution and registers it with the
+workspace, the runner and the benchmarks. Its examples go in examples/dayNN,
+each <name>.txt beside a <name>.answers file of '<part> <answer>' lines, and
+the runner's tests check them all.
+watch re-runs a day's tests and then its solver, built with --release, every
+time something in dayNN/src or dayNN/input changes, comparing the answers and
+timings with the last run's.
+gen prints a random input for a day that has a generator, the same one every
+time for a --seed (default 1), at a scale set by --size (default 100). Any
+answers the generator knows are printed to stderr, or with --answers written
+to a file in the form the examples use.";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Selection {
+    All,
+    Day(u8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run {
+        selection: Selection,
+        part: Option<u8>,
+        input: Option<Input>,
+        format: Format,
+        jobs: usize,
+        budget: Duration,
+        verbosity: u8,
+    },
+    Verify {
+        selection: Selection,
+        input: Option<Input>,
+        answers: Option<PathBuf>,
+        record: bool,
+    },
+    Fetch {
+        day: u8,
+    },
+    Submit {
+        day: u8,
+        part: u8,
+    },
+    New {
+        day: u8,
+    },
+    Watch {
+        day: u8,
+    },
+    Gen {
+        day: u8,
+        seed: u64,
+        size: usize,
+        answers: Option<PathBuf>,
+    },
+}
+
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Command> {
+    let mut args = args.into_iter();
+
+    match args.next().as_deref() {
+        Some("run") => parse_run(args),
+        Some("verify") => parse_verify(args),
+        Some("fetch") => parse_fetch(args),
+        Some("submit") => parse_submit(args),
+        Some("new") => parse_new(args),
+        Some("watch") => parse_watch(args),
+        Some("gen") => parse_gen(args),
+        Some(other) => Err(format!("unknown command {other:?}").into()),
+        None => Err("no command given".into()),
+    }
+}
+
+/// One job per CPU, or just the one if that can't be found out.
+fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, |jobs| jobs.get())
+}
+
+fn parse_run(mut args: impl Iterator<Item = String>) -> Result<Command> {
+    let mut selection = None;
+    let mut part = None;
+    let mut input = None;
+    let mut format = Format::Text;
+    let mut jobs = default_jobs();
+    let mut budget = Duration::from_secs(1);
+    let mut verbosity: u8 = 0;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => selection = Some(Selection::All),
+            "--explain" => verbosity = 3,
+            flag if flag.starts_with("-v") && flag[1..].bytes().all(|b| b == b'v') => {
+                verbosity = verbosity.saturating_add((flag.len() - 1).min(u8::MAX as usize) as u8);
+            }
+            "--part" => {
+                let value = args.next().ok_or("--part needs a value")?;
+                part = match value.as_str() {
+                    "1" => Some(1),
+                    "2" => Some(2),
+                    _ => return Err(format!("part must be 1 or 2, got {value:?}").into()),
+                };
+            }
+            "--input" => {
+                let value = args.next().ok_or("--input needs a path or -")?;
+                input = Some(Input::from_arg(&value));
+            }
+            "--format" => {
+                let value = args.next().ok_or("--format needs a value")?;
+                format = match value.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    _ => return Err(format!("format must be text or json, got {value:?}").into()),
+                };
+            }
+            "--jobs" => {
+                let value = args.next().ok_or("--jobs needs a value")?;
+                jobs = match value.parse() {
+                    Ok(jobs) if jobs > 0 => jobs,
+                    _ => {
+                        return Err(format!("jobs must be a positive number, got {value:?}").into())
+                    }
+                };
+            }
+            "--budget" => {
+                let value = args.next().ok_or("--budget needs a value")?;
+                let millis = value.parse().map_err(|_| {
+                    format!("budget must be a number of milliseconds, got {value:?}")
+                })?;
+                budget = Duration::from_millis(millis);
+            }
+            day if selection.is_none() => {
+                let day = day
+                    .parse()
+                    .map_err(|_| format!("expected a day number, got {day:?}"))?;
+                selection = Some(Selection::Day(day));
+            }
+            other => return Err(format!("unexpected argument {other:?}").into()),
+        }
+    }
+
+    let selection = selection.ok_or("run needs a day or --all")?;
+    if input.is_some() && selection == Selection::All {
+        return Err("--input can't be used with --all".into());
+    }
+
+    Ok(Command::Run {
+        selection,
+        part,
+        input,
+        format,
+        jobs,
+        budget,
+        verbosity,
+    })
+}
+
+fn parse_verify(mut args: impl Iterator<Item = String>) -> Result<Command> {
+    let mut selection = Selection::All;
+    let mut input = None;
+    let mut answers = None;
+    let mut record = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" => {
+                let value = args.next().ok_or("--input needs a path or -")?;
+                input = Some(Input::from_arg(&value));
+            }
+            "--answers" => {
+                let value = args.next().ok_or("--answers needs a path")?;
+                answers = Some(value.into());
+            }
+            "--record" => record = true,
+            day if selection == Selection::All => {
+                let day = day
+                    .parse()
+                    .map_err(|_| format!("expected a day number, got {day:?}"))?;
+                selection = Selection::Day(day);
+            }
+            other => return Err(format!("unexpected argument {other:?}").into()),
+        }
+    }
+
+    if input.is_some() && selection == Selection::All {
+        return Err("--input needs a day to verify".into());
+    }
+
+    Ok(Command::Verify {
+        selection,
+        input,
+        answers,
+        record,
+    })
+}
+
+/// A puzzle day, which unlike the days we can run has to exist on the site.
+fn parse_day(day: &str) -> Result<u8> {
+    match day.parse() {
+        Ok(day @ 1..=25) => Ok(day),
+        _ => Err(format!("expected a day from 1 to 25, got {day:?}").into()),
+    }
+}
+
+fn parse_fetch(mut args: impl Iterator<Item = String>) -> Result<Command> {
+    let day = parse_day(&args.next().ok_or("fetch needs a day")?)?;
+    if let Some(other) = args.next() {
+        return Err(format!("unexpected argument {other:?}").into());
+    }
+
+    Ok(Command::Fetch { day })
+}
+
+fn parse_submit(mut args: impl Iterator<Item = String>) -> Result<Command> {
+    let day = parse_day(&args.next().ok_or("submit needs a day and a part")?)?;
+    let part = match args.next().ok_or("submit needs a part")?.as_str() {
+        "1" => 1,
+        "2" => 2,
+        value => return Err(format!("part must be 1 or 2, got {value:?}").into()),
+    };
+    if let Some(other) = args.next() {
+        return Err(format!("unexpected argument {other:?}").into());
+    }
+
+    Ok(Command::Submit { day, part })
+}
+
+fn parse_new(mut args: impl Iterator<Item = String>) -> Result<Command> {
+    let day = parse_day(&args.next().ok_or("new needs a day")?)?;
+    if let Some(other) = args.next() {
+        return Err(format!("unexpected argument {other:?}").into());
+    }
+
+    Ok(Command::New { day })
+}
+
+fn parse_watch(mut args: impl Iterator<Item = String>) -> Result<Command> {
+    let day = parse_day(&args.next().ok_or("watch needs a day")?)?;
+    if let Some(other) = args.next() {
+        return Err(format!("unexpected argument {other:?}").into());
+    }
+
+    Ok(Command::Watch { day })
+}
+
+fn parse_gen(mut args: impl Iterator<Item = String>) -> Result<Command> {
+    let mut day = None;
+    let mut seed = 1;
+    let mut size = 100;
+    let mut answers = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => {
+                let value = args.next().ok_or("--seed needs a value")?;
+                seed = value
+                    .parse()
+                    .map_err(|_| format!("seed must be a number, got {value:?}"))?;
+            }
+            "--size" => {
+                let value = args.next().ok_or("--size needs a value")?;
+                size = value
+                    .parse()
+                    .map_err(|_| format!("size must be a number, got {value:?}"))?;
+            }
+            "--answers" => {
+                let value = args.next().ok_or("--answers needs a path")?;
+                answers = Some(value.into());
+            }
+            value if day.is_none() => day = Some(parse_day(value)?),
+            other => return Err(format!("unexpected argument {other:?}").into()),
+        }
+    }
+
+    Ok(Command::Gen {
+        day: day.ok_or("gen needs a day")?,
+        seed,
+        size,
+        answers,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Command> {
+        parse_args(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_run() {
+        assert_eq!(
+            parse("run 7 --part 2").unwrap(),
+            Command::Run {
+                selection: Selection::Day(7),
+                part: Some(2),
+                input: None,
+                format: Format::Text,
+                jobs: default_jobs(),
+                budget: Duration::from_secs(1),
+                verbosity: 0,
+            }
+        );
+        assert_eq!(
+            parse("run --all").unwrap(),
+            Command::Run {
+                selection: Selection::All,
+                part: None,
+                input: None,
+                format: Format::Text,
+                jobs: default_jobs(),
+                budget: Duration::from_secs(1),
+                verbosity: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(
+            parse("run 7 --input -").unwrap(),
+            Command::Run {
+                selection: Selection::Day(7),
+                part: None,
+                input: Some(Input::Stdin),
+                format: Format::Text,
+                jobs: default_jobs(),
+                budget: Duration::from_secs(1),
+                verbosity: 0,
+            }
+        );
+        assert_eq!(
+            parse("run --input /tmp/day7.txt 7 --part 1").unwrap(),
+            Command::Run {
+                selection: Selection::Day(7),
+                part: Some(1),
+                input: Some(Input::File("/tmp/day7.txt".into())),
+                format: Format::Text,
+                jobs: default_jobs(),
+                budget: Duration::from_secs(1),
+                verbosity: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(
+            parse("run --all --format json").unwrap(),
+            Command::Run {
+                selection: Selection::All,
+                part: None,
+                input: None,
+                format: Format::Json,
+                jobs: default_jobs(),
+                budget: Duration::from_secs(1),
+                verbosity: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_jobs() {
+        assert_eq!(
+            parse("run --all --jobs 4 --budget 250").unwrap(),
+            Command::Run {
+                selection: Selection::All,
+                part: None,
+                input: None,
+                format: Format::Text,
+                jobs: 4,
+                budget: Duration::from_millis(250),
+                verbosity: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_verbosity() {
+        let verbosity = |args| match parse(args).unwrap() {
+            Command::Run { verbosity, .. } => verbosity,
+            command => panic!("{command:?}"),
+        };
+        assert_eq!(verbosity("run 7"), 0);
+        assert_eq!(verbosity("run 7 -v"), 1);
+        assert_eq!(verbosity("run -v 7 -v"), 2);
+        assert_eq!(verbosity("run 7 -vvv"), 3);
+        assert_eq!(verbosity("run --all --explain"), 3);
+        let many = format!("run 7 -{} -v", "v".repeat(300));
+        assert_eq!(verbosity(&many), u8::MAX);
+    }
+
+    #[test]
+    fn test_parse_verify() {
+        assert_eq!(
+            parse("verify").unwrap(),
+            Command::Verify {
+                selection: Selection::All,
+                input: None,
+                answers: None,
+                record: false,
+            }
+        );
+        assert_eq!(
+            parse("verify 7 --input - --answers /tmp/answers --record").unwrap(),
+            Command::Verify {
+                selection: Selection::Day(7),
+                input: Some(Input::Stdin),
+                answers: Some("/tmp/answers".into()),
+                record: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_fetch() {
+        assert_eq!(parse("fetch 12").unwrap(), Command::Fetch { day: 12 });
+    }
+
+    #[test]
+    fn test_parse_submit() {
+        assert_eq!(
+            parse("submit 7 2").unwrap(),
+            Command::Submit { day: 7, part: 2 }
+        );
+    }
+
+    #[test]
+    fn test_parse_new() {
+        assert_eq!(parse("new 12").unwrap(), Command::New { day: 12 });
+    }
+
+    #[test]
+    fn test_parse_watch() {
+        assert_eq!(parse("watch 7").unwrap(), Command::Watch { day: 7 });
+    }
+
+    #[test]
+    fn test_parse_gen() {
+        assert_eq!(
+            parse("gen 7").unwrap(),
+            Command::Gen {
+                day: 7,
+                seed: 1,
+                size: 100,
+                answers: None,
+            }
+        );
+        assert_eq!(
+            parse("gen --seed 42 8 --size 5000 --answers /tmp/gen.answers").unwrap(),
+            Command::Gen {
+                day: 8,
+                seed: 42,
+                size: 5000,
+                answers: Some("/tmp/gen.answers".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("").is_err());
+        assert!(parse("run").is_err());
+        assert!(parse("run seven").is_err());
+        assert!(parse("run 7 --part 3").is_err());
+        assert!(parse("run 7 8").is_err());
+        assert!(parse("run 7 --input").is_err());
+        assert!(parse("run --all --input -").is_err());
+        assert!(parse("run 7 --format yaml").is_err());
+        assert!(parse("run --all --jobs 0").is_err());
+        assert!(parse("run --all --budget 1s").is_err());
+        assert!(parse("run 7 -x").is_err());
+        assert!(parse("verify --input -").is_err());
+        assert!(parse("verify 7 8").is_err());
+        assert!(parse("verify --answers").is_err());
+        assert!(parse("fetch").is_err());
+        assert!(parse("fetch 26").is_err());
+        assert!(parse("fetch 7 8").is_err());
+        assert!(parse("submit 7").is_err());
+        assert!(parse("submit 7 3").is_err());
+        assert!(parse("submit 0 1").is_err());
+        assert!(parse("submit 7 1 6440").is_err());
+        assert!(parse("new").is_err());
+        assert!(parse("new 12 13").is_err());
+        assert!(parse("watch").is_err());
+        assert!(parse("gen").is_err());
+        assert!(parse("gen 7 --seed x").is_err());
+        assert!(parse("gen 7 --size -1").is_err());
+        assert!(parse("gen 7 8").is_err());
+    }
+}
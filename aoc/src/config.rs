@@ -0,0 +1,244 @@
+use aoc_common::{parse_number, ParseError, Result};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const DEFAULT_YEAR: u16 = 2023;
+
+/// How to reach the puzzle site, and where to keep what we get from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+    pub year: u16,
+    pub cache_dir: Option<PathBuf>,
+    /// Where the settings were read from, to point at in error messages.
+    pub path: Option<PathBuf>,
+}
+
+/// Settings from the config file, before the environment gets its say.
+///
+/// The file has one `<key> = <value>` per line, with blank lines and `#`
+/// comments ignored. The keys are `session`, `base-url`, `year` and `cache`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Settings {
+    session: Option<String>,
+    base_url: Option<String>,
+    year: Option<u16>,
+    cache_dir: Option<PathBuf>,
+}
+
+impl FromStr for Settings {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut settings = Settings::default();
+        for line in s.lines() {
+            let content = line.split('#').next().unwrap().trim();
+            if content.is_empty() {
+                continue;
+            }
+
+            let Some((key, value)) = content.split_once('=') else {
+                return Err(ParseError::expected(s, content, "'<key> = <value>'"));
+            };
+            let (key, value) = (key.trim_end(), value.trim_start());
+            if value.is_empty() {
+                return Err(ParseError::invalid(s, content, "missing value"));
+            }
+            match key {
+                "session" => settings.session = Some(value.into()),
+                "base-url" => settings.base_url = Some(value.into()),
+                "year" => settings.year = Some(parse_number(s, value)?),
+                "cache" => settings.cache_dir = Some(value.into()),
+                _ => return Err(ParseError::invalid(s, key, "unknown setting")),
+            }
+        }
+
+        Ok(settings)
+    }
+}
+
+impl Settings {
+    /// Reads the settings at `path`, treating a missing file as empty.
+    pub fn load(path: &Path) -> Result<Settings> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(contents
+                .parse()
+                .map_err(|err| format!("{}:{err}", path.display()))?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Settings::default()),
+            Err(err) => Err(format!("{}: couldn't read config: {err}", path.display()).into()),
+        }
+    }
+}
+
+impl Config {
+    /// Reads the config file, `$AOC_CONFIG` or else `aoc/config` under the
+    /// user's config directory, then applies overrides from the environment.
+    pub fn load() -> Result<Config> {
+        let var = |name: &str| env::var(name).ok().filter(|value| !value.is_empty());
+        let path = match var("AOC_CONFIG") {
+            Some(path) => Some(PathBuf::from(path)),
+            None => user_dir(var("XDG_CONFIG_HOME"), var("HOME"), ".config")
+                .map(|dir| dir.join("config")),
+        };
+        let settings = match &path {
+            Some(path) => Settings::load(path)?,
+            None => Settings::default(),
+        };
+        Ok(Config::resolve(settings, path, var))
+    }
+
+    /// Combines `settings` from the file at `path` with the environment, as
+    /// looked up by `var`. `AOC_SESSION`, `AOC_BASE_URL` and `AOC_CACHE_DIR`
+    /// win over the file; the cache otherwise defaults to `aoc` under the
+    /// user's cache directory.
+    pub fn resolve(
+        settings: Settings,
+        path: Option<PathBuf>,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> Config {
+        let cache_dir = match var("AOC_CACHE_DIR") {
+            Some(dir) => Some(dir.into()),
+            None => settings
+                .cache_dir
+                .or_else(|| user_dir(var("XDG_CACHE_HOME"), var("HOME"), ".cache")),
+        };
+        let base_url = var("AOC_BASE_URL")
+            .or(settings.base_url)
+            .unwrap_or_else(|| DEFAULT_BASE_URL.into());
+
+        Config {
+            session: var("AOC_SESSION").or(settings.session),
+            base_url: base_url.trim_end_matches('/').into(),
+            year: settings.year.unwrap_or(DEFAULT_YEAR),
+            cache_dir,
+            path,
+        }
+    }
+
+    /// The session token, or an error saying how to provide one.
+    pub fn session(&self) -> Result<&str> {
+        match (&self.session, &self.path) {
+            (Some(session), _) => Ok(session),
+            (None, Some(path)) => Err(format!(
+                "no session token: set AOC_SESSION or add 'session = <token>' to {}",
+                path.display()
+            )
+            .into()),
+            (None, None) => Err("no session token: set AOC_SESSION".into()),
+        }
+    }
+
+    /// The URL of `path` on the puzzle site for the configured year.
+    pub fn url(&self, path: &str) -> String {
+        format!("{}/{}/{path}", self.base_url, self.year)
+    }
+
+    /// Where cached files for the configured year live.
+    pub fn cache(&self) -> Result<PathBuf> {
+        let dir = self
+            .cache_dir
+            .as_ref()
+            .ok_or("couldn't find a cache directory: set AOC_CACHE_DIR")?;
+        Ok(dir.join(self.year.to_string()))
+    }
+}
+
+/// The `aoc` directory under an XDG base directory, falling back to `default`
+/// in the `home` directory like the spec says.
+fn user_dir(xdg: Option<String>, home: Option<String>, default: &str) -> Option<PathBuf> {
+    let base = match xdg {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(home?).join(default),
+    };
+    Some(base.join("aoc"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_parse() {
+        let settings: Settings = "\
+# aoc settings
+session = 53616c7465645f5f
+
+base-url = http://localhost:8080/  # for testing
+year = 2022
+"
+        .parse()
+        .unwrap();
+        assert_eq!(
+            settings,
+            Settings {
+                session: Some("53616c7465645f5f".into()),
+                base_url: Some("http://localhost:8080/".into()),
+                year: Some(2022),
+                cache_dir: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = "session 1234\n".parse::<Settings>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "1:1: expected '<key> = <value>', found 'session 1234'"
+        );
+
+        let err = "year = 2023\ntoken = 1234\n"
+            .parse::<Settings>()
+            .unwrap_err();
+        assert_eq!(err.to_string(), "2:1: unknown setting: 'token'");
+
+        let err = "year = twenty\n".parse::<Settings>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "1:8: invalid number 'twenty': invalid digit found in string"
+        );
+
+        let err = "session =\n".parse::<Settings>().unwrap_err();
+        assert_eq!(err.to_string(), "1:1: missing value: 'session ='");
+    }
+
+    #[test]
+    fn test_resolve() {
+        let settings = "session = file\nbase-url = http://file/\ncache = /file/cache"
+            .parse()
+            .unwrap();
+        let env: HashMap<&str, &str> = [("AOC_SESSION", "env"), ("HOME", "/home/elf")].into();
+        let config = Config::resolve(settings, None, |name| {
+            env.get(name).map(|value| value.to_string())
+        });
+
+        assert_eq!(config.session().unwrap(), "env");
+        assert_eq!(config.url("day/7/input"), "http://file/2023/day/7/input");
+        assert_eq!(config.cache().unwrap(), Path::new("/file/cache/2023"));
+    }
+
+    #[test]
+    fn test_defaults() {
+        let path = PathBuf::from("/home/elf/.config/aoc/config");
+        let config = Config::resolve(Settings::default(), Some(path), |name| {
+            (name == "XDG_CACHE_HOME").then(|| "/tmp/cache".into())
+        });
+
+        assert_eq!(
+            config.url("day/1/input"),
+            "https://adventofcode.com/2023/day/1/input"
+        );
+        assert_eq!(config.cache().unwrap(), Path::new("/tmp/cache/aoc/2023"));
+        assert_eq!(
+            config.session().unwrap_err().to_string(),
+            "no session token: set AOC_SESSION or add 'session = <token>' to \
+             /home/elf/.config/aoc/config"
+        );
+    }
+}
@@ -0,0 +1,32 @@
+use aoc_common::{Input, Solver};
+use std::path::Path;
+
+pub const DAYS: &[&dyn Solver] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+];
+
+pub fn find(number: u8) -> Option<&'static dyn Solver> {
+    DAYS.iter().copied().find(|day| day.day() == number)
+}
+
+/// The workspace `aoc` was built in, so it can find the day crates from
+/// anywhere.
+pub fn workspace() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+/// The day's `input` file, found relative to the workspace rather than the
+/// current directory so `aoc` can be run from anywhere.
+pub fn default_input(number: u8) -> Input {
+    Input::File(workspace().join(format!("day{number:02}")).join("input"))
+}
@@ -0,0 +1,244 @@
+//! The puzzle examples in `examples/`, checked against the solvers.
+//!
+//! Each day has a directory like `examples/day07` holding examples named
+//! `<name>.txt`, each next to a `<name>.answers` file with the answers it
+//! should give. Adding a regression case is a matter of adding the two files.
+
+use crate::days;
+use aoc_common::{input, parse_number, ParseError, Result, Solver};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// The answers an example should give, keyed by part.
+///
+/// The file has one answer per line, as `<part> <answer>`, with blank lines
+/// and `#` comments ignored. Parts without an answer aren't checked, since
+/// some examples only make sense for one of them.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Expected {
+    answers: BTreeMap<u8, String>,
+}
+
+impl FromStr for Expected {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = BTreeMap::new();
+        for line in s.lines() {
+            let content = line.split('#').next().unwrap().trim();
+            if content.is_empty() {
+                continue;
+            }
+
+            let Some((part, answer)) = content.split_once(char::is_whitespace) else {
+                return Err(ParseError::expected(s, content, "'<part> <answer>'"));
+            };
+            let part = parse_number(s, part)?;
+            if !(1..=2).contains(&part) {
+                return Err(ParseError::invalid(
+                    s,
+                    content,
+                    "there are only parts 1 and 2",
+                ));
+            }
+            if answers.insert(part, answer.trim().into()).is_some() {
+                return Err(ParseError::invalid(s, content, "duplicate answer"));
+            }
+        }
+
+        Ok(Expected { answers })
+    }
+}
+
+/// An example from the corpus.
+#[derive(Debug)]
+struct Example {
+    day: u8,
+    path: PathBuf,
+    input: String,
+    expected: Expected,
+}
+
+/// The `examples` directory in the workspace root.
+fn default_dir() -> PathBuf {
+    days::workspace().join("examples")
+}
+
+/// Reads every example under `dir`, in order of day and name. An example
+/// without answers, or answers without an example, is an error rather than
+/// something to skip quietly.
+fn corpus(dir: &Path) -> Result<Vec<Example>> {
+    let read_dir = |dir: &Path| -> Result<Vec<PathBuf>> {
+        let mut paths = fs::read_dir(dir)
+            .map_err(|err| format!("{}: couldn't read examples: {err}", dir.display()))?
+            .map(|entry| Ok(entry?.path()))
+            .collect::<Result<Vec<_>>>()?;
+        paths.sort();
+        Ok(paths)
+    };
+
+    let mut examples = Vec::new();
+    for day_dir in read_dir(dir)? {
+        let day = day_dir
+            .file_name()
+            .and_then(|name| name.to_str()?.strip_prefix("day")?.parse().ok())
+            .ok_or_else(|| format!("{}: expected a directory like 'day07'", day_dir.display()))?;
+
+        for path in read_dir(&day_dir)? {
+            let sidecar = match path.extension().and_then(|extension| extension.to_str()) {
+                Some("txt") => path.with_extension("answers"),
+                Some("answers") if path.with_extension("txt").exists() => continue,
+                _ => return Err(format!("{}: isn't a '.txt' example", path.display()).into()),
+            };
+            let read = |path: &Path| {
+                fs::read_to_string(path)
+                    .map_err(|err| format!("{}: couldn't read: {err}", path.display()))
+            };
+            let expected = read(&sidecar)?
+                .parse()
+                .map_err(|err| format!("{}:{err}", sidecar.display()))?;
+            examples.push(Example {
+                day,
+                input: read(&path)?,
+                path,
+                expected,
+            });
+        }
+    }
+    Ok(examples)
+}
+
+impl Example {
+    /// Checks the example against `solver`, as given and in each of the ways
+    /// it could be mangled in transit, describing the first way it fails.
+    fn check(&self, solver: &dyn Solver) -> Result<(), String> {
+        let parts: Vec<u8> = self.expected.answers.keys().copied().collect();
+        let mangled = input::variants(&self.input).into_iter().map(Some);
+        for variant in [None].into_iter().chain(mangled) {
+            let input = variant.as_deref().unwrap_or(&self.input);
+            let described = |problem: String| match &variant {
+                Some(variant) => format!("{problem}, given {variant:?}"),
+                None => problem,
+            };
+
+            let answers = solver
+                .solve(input, &parts)
+                .map_err(|err| described(format!("failed: {err}")))?;
+            for (answer, (part, expected)) in answers.iter().zip(&self.expected.answers) {
+                if answer.to_string() != *expected {
+                    return Err(described(format!(
+                        "part {part} gave {answer}, expected {expected}"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_parse() {
+        let expected: Expected = "# part answer\n1 6440\n\n2 5905  # with jokers\n"
+            .parse()
+            .unwrap();
+        assert_eq!(expected.answers[&1], "6440");
+        assert_eq!(expected.answers[&2], "5905");
+
+        let expected: Expected = "2 two words\n".parse().unwrap();
+        assert_eq!(expected.answers[&2], "two words");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = "6440\n".parse::<Expected>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "1:1: expected '<part> <answer>', found '6440'"
+        );
+
+        let err = "1 6440\n3 5905\n".parse::<Expected>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "2:1: there are only parts 1 and 2: '3 5905'"
+        );
+
+        let err = "1 6440\n1 5905\n".parse::<Expected>().unwrap_err();
+        assert_eq!(err.to_string(), "2:1: duplicate answer: '1 5905'");
+    }
+
+    #[test]
+    fn test_corpus_errors() {
+        let dir = env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("day07")).unwrap();
+        fs::write(dir.join("day07/example.txt"), "32T3K 765\n").unwrap();
+
+        let err = corpus(&dir).unwrap_err().to_string();
+        assert!(
+            err.ends_with("example.answers: couldn't read: No such file or directory (os error 2)"),
+            "{err}"
+        );
+
+        fs::write(dir.join("day07/example.answers"), "1 6440\n").unwrap();
+        let examples = corpus(&dir).unwrap();
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].day, 7);
+        assert_eq!(examples[0].input, "32T3K 765\n");
+
+        let err = examples[0].check(&day7::Day7).unwrap_err();
+        assert_eq!(err, "part 1 gave 765, expected 6440");
+
+        fs::write(dir.join("day07/stray.answers"), "1 765\n").unwrap();
+        let err = corpus(&dir).unwrap_err().to_string();
+        assert!(
+            err.ends_with("stray.answers: isn't a '.txt' example"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_examples() {
+        // `aoc watch` names the one day it wants checked
+        let only: Option<u8> = env::var("AOC_EXAMPLES_DAY")
+            .ok()
+            .map(|day| day.parse().expect("AOC_EXAMPLES_DAY should be a day"));
+        let checked = |day: u8| only.is_none_or(|only| day == only);
+
+        let examples: Vec<Example> = corpus(&default_dir())
+            .unwrap()
+            .into_iter()
+            .filter(|example| checked(example.day))
+            .collect();
+        let mut failures = Vec::new();
+        for example in &examples {
+            let Some(solver) = days::find(example.day) else {
+                failures.push(format!(
+                    "{}: there's no day {}",
+                    example.path.display(),
+                    example.day
+                ));
+                continue;
+            };
+            if let Err(err) = example.check(solver) {
+                failures.push(format!("{}: {err}", example.path.display()));
+            }
+        }
+        assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+
+        // every day has at least one example
+        for day in days::DAYS.iter().filter(|day| checked(day.day())) {
+            assert!(
+                examples.iter().any(|example| example.day == day.day()),
+                "day {} has no examples",
+                day.day()
+            );
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use crate::config::Config;
+use crate::site;
+use aoc_common::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Whether an input had to be downloaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+/// Where `day`'s input is cached.
+pub fn cached_path(config: &Config, day: u8) -> Result<PathBuf> {
+    Ok(config.cache()?.join(format!("day{day:02}")))
+}
+
+/// Makes sure `day`'s input is in the cache, returning where. Inputs never
+/// change once published, so a cached one is never downloaded again.
+pub fn fetch(config: &Config, day: u8) -> Result<(PathBuf, Fetched)> {
+    let path = cached_path(config, day)?;
+    if path.exists() {
+        return Ok((path, Fetched::Cached));
+    }
+
+    let input = site::get(config, &format!("day/{day}/input"))?;
+    if input.trim().is_empty() {
+        return Err(format!("the site sent an empty input for day {day}").into());
+    }
+
+    // write somewhere else first so an interrupted download can't leave a
+    // truncated input that would then be cached for good
+    let dir = path.parent().unwrap();
+    fs::create_dir_all(dir)
+        .map_err(|err| format!("{}: couldn't create cache: {err}", dir.display()))?;
+    let partial = path.with_extension("partial");
+    fs::write(&partial, input)
+        .and_then(|()| fs::rename(&partial, &path))
+        .map_err(|err| format!("{}: couldn't cache input: {err}", path.display()))?;
+
+    Ok((path, Fetched::Downloaded))
+}
+
+/// Copies the cached input to `target`, a day's `input` file, unless there's
+/// already one there or the day has no crate yet. Says whether it copied.
+pub fn install(cached: &Path, target: &Path) -> Result<bool> {
+    if target.exists() || !target.parent().is_some_and(Path::is_dir) {
+        return Ok(false);
+    }
+    fs::copy(cached, target)
+        .map_err(|err| format!("{}: couldn't copy input: {err}", target.display()))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::Settings;
+    use crate::mock::Server;
+    use std::env;
+    use std::net::TcpListener;
+
+    /// A config pointing at `url` with a cache of its own under the temp dir.
+    fn config(url: &str, name: &str) -> Config {
+        let cache = env::temp_dir().join(format!("aoc-fetch-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&cache);
+        Config::resolve(Settings::default(), None, |var| match var {
+            "AOC_SESSION" => Some("cafe".into()),
+            "AOC_BASE_URL" => Some(url.into()),
+            "AOC_CACHE_DIR" => Some(cache.to_string_lossy().into()),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_fetch() {
+        let server = Server::start(vec![(200, "32T3K 765\n")]);
+        let config = config(server.url(), "fetch");
+
+        let (path, fetched) = fetch(&config, 7).unwrap();
+        assert_eq!(fetched, Fetched::Downloaded);
+        assert!(path.ends_with("2023/day07"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "32T3K 765\n");
+
+        // the second fetch is served from the cache without asking again
+        assert_eq!(fetch(&config, 7).unwrap(), (path, Fetched::Cached));
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2023/day/7/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("\r\nCookie: session=cafe\r\n"));
+    }
+
+    #[test]
+    fn test_fetch_errors() {
+        let server = Server::start(vec![
+            (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+            ),
+            (404, "404 Not Found\n"),
+            (500, "Internal Server Error\n"),
+            (200, ""),
+        ]);
+        let config = config(server.url(), "errors");
+
+        assert_eq!(
+            fetch(&config, 1).unwrap_err().to_string(),
+            "the session token was rejected (HTTP 400); it has probably expired, \
+             so log in again and update it"
+        );
+        assert_eq!(
+            fetch(&config, 25).unwrap_err().to_string(),
+            format!(
+                "{}/2023/day/25/input wasn't found (HTTP 404); the puzzle may not be unlocked yet",
+                server.url()
+            )
+        );
+        assert_eq!(
+            fetch(&config, 2).unwrap_err().to_string(),
+            format!(
+                "{}/2023/day/2/input failed with HTTP 500: Internal Server Error",
+                server.url()
+            )
+        );
+        assert_eq!(
+            fetch(&config, 3).unwrap_err().to_string(),
+            "the site sent an empty input for day 3"
+        );
+        // none of the failures left anything in the cache
+        assert!(!cached_path(&config, 3).unwrap().exists());
+    }
+
+    #[test]
+    fn test_unreachable() {
+        // bind and drop a listener to find a port nothing is listening on
+        let url = format!(
+            "http://{}",
+            TcpListener::bind("127.0.0.1:0")
+                .unwrap()
+                .local_addr()
+                .unwrap()
+        );
+        let err = fetch(&config(&url, "unreachable"), 1).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with(&format!("couldn't reach {url}/2023/day/1/input: ")),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_install() {
+        let dir = env::temp_dir().join(format!("aoc-install-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("day07")).unwrap();
+        let cached = dir.join("cached");
+        fs::write(&cached, "32T3K 765\n").unwrap();
+
+        let target = dir.join("day07").join("input");
+        assert!(install(&cached, &target).unwrap());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "32T3K 765\n");
+
+        // an existing input is left alone, and so is a day without a crate
+        fs::write(&target, "edited\n").unwrap();
+        assert!(!install(&cached, &target).unwrap());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "edited\n");
+        assert!(!install(&cached, &dir.join("day08").join("input")).unwrap());
+    }
+}
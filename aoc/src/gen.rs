@@ -0,0 +1,85 @@
+use crate::days;
+use aoc_common::{Generated, Result};
+use std::fs;
+use std::path::Path;
+
+/// The answers a generator worked out, as an examples `.answers` file that
+/// says how to generate its input again.
+fn answers_file(day: u8, seed: u64, size: usize, generated: &Generated) -> String {
+    let mut file = format!("# part answer\n# aoc gen {day} --seed {seed} --size {size}\n");
+    for (part, answer) in &generated.answers {
+        file += &format!("{part} {answer}\n");
+    }
+    file
+}
+
+/// Prints a generated input for `day` to stdout, with whatever answers the
+/// generator knows on stderr, or written to `answers` if it's given.
+pub fn gen(day: u8, seed: u64, size: usize, answers: Option<&Path>) -> Result<bool> {
+    let solver = days::find(day).ok_or(format!("day {day} is not implemented"))?;
+    let generated = solver
+        .generate(seed, size)
+        .ok_or(format!("day {day} has no input generator"))?;
+
+    print!("{}", generated.input);
+    match answers {
+        Some(path) => fs::write(path, answers_file(day, seed, size, &generated))
+            .map_err(|err| format!("{}: couldn't write answers: {err}", path.display()))?,
+        None => {
+            for (part, answer) in &generated.answers {
+                eprintln!("part {part}: {answer}");
+            }
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::Answer;
+
+    #[test]
+    fn test_answers_file() {
+        let generated = Generated {
+            input: "32T3K 765\n".into(),
+            answers: vec![(1, Answer::Unsigned(765)), (2, Answer::Unsigned(765))],
+        };
+        assert_eq!(
+            answers_file(7, 3, 1, &generated),
+            "# part answer\n# aoc gen 7 --seed 3 --size 1\n1 765\n2 765\n"
+        );
+    }
+
+    #[test]
+    fn test_generators() {
+        let mut generators = 0;
+        for solver in days::DAYS {
+            for seed in 0..8 {
+                for size in [0, 1, 10, 60] {
+                    let Some(generated) = solver.generate(seed, size) else {
+                        continue;
+                    };
+                    generators += 1;
+                    assert_eq!(solver.generate(seed, size).as_ref(), Some(&generated));
+
+                    // the answers the generator worked out agree with the solver's
+                    let context = format!("day {} --seed {seed} --size {size}", solver.day());
+                    let parts: Vec<u8> = generated.answers.iter().map(|&(part, _)| part).collect();
+                    let answers = solver
+                        .solve(&generated.input, &parts)
+                        .unwrap_or_else(|err| panic!("{context}: {err}\n{}", generated.input));
+                    for ((part, expected), answer) in generated.answers.iter().zip(answers) {
+                        assert_eq!(
+                            answer.to_string(),
+                            expected.to_string(),
+                            "{context} part {part}\n{}",
+                            generated.input
+                        );
+                    }
+                }
+            }
+        }
+        assert!(generators > 0);
+    }
+}
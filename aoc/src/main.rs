@@ -0,0 +1,244 @@
+mod answers;
+mod cli;
+mod config;
+mod days;
+#[cfg(test)]
+mod examples;
+mod fetch;
+mod gen;
+#[cfg(test)]
+mod mock;
+mod pool;
+mod report;
+mod scaffold;
+mod site;
+mod submit;
+mod verify;
+mod watch;
+
+use aoc_common::{Input, ParseError, Result, Solver};
+use cli::{Command, Format, Selection};
+use config::Config;
+use days::DAYS;
+use report::Record;
+use std::error::Error;
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime};
+use submit::History;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::fmt::time::Uptime;
+
+/// Points parse errors at the input they came from, like `input:14:7: ...`.
+fn locate(source: &Input, err: &(dyn Error + 'static)) -> String {
+    match err.downcast_ref::<ParseError>() {
+        Some(err) => format!("{source}:{err}"),
+        None => err.to_string(),
+    }
+}
+
+fn select(selection: Selection) -> Result<Vec<&'static dyn Solver>> {
+    match selection {
+        Selection::All => Ok(DAYS.to_vec()),
+        Selection::Day(number) => Ok(vec![
+            days::find(number).ok_or(format!("day {number} is not implemented"))?
+        ]),
+    }
+}
+
+/// Reads `day`'s input and solves the requested `parts` of it. Failures,
+/// including unreadable input, come back as failed records.
+fn solve(day: &dyn Solver, input: Option<&Input>, parts: &[u8]) -> Vec<Record> {
+    let source = input
+        .cloned()
+        .unwrap_or_else(|| days::default_input(day.day()));
+    match source.read() {
+        Ok(contents) => {
+            let run = day.run(&contents, parts);
+            Record::from_run(day.day(), parts, run, |err| locate(&source, err))
+        }
+        Err(err) => Record::failed(day.day(), parts, err.to_string(), None),
+    }
+}
+
+/// Solves the selected days on up to `jobs` threads, then prints each answer,
+/// or why there isn't one, in order. Text output for `--all` finishes with a
+/// table of timings. The result says whether everything succeeded.
+fn run(
+    selection: Selection,
+    part: Option<u8>,
+    input: Option<Input>,
+    format: Format,
+    jobs: usize,
+    budget: Duration,
+) -> Result<bool> {
+    let all = selection == Selection::All;
+    let days = select(selection)?;
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    let records: Vec<Record> = pool::map(&days, jobs, |day| solve(*day, input.as_ref(), &parts))
+        .into_iter()
+        .zip(&days)
+        .flat_map(|(records, day)| {
+            records.unwrap_or_else(|panic| {
+                Record::failed(day.day(), &parts, format!("panicked: {panic}"), None)
+            })
+        })
+        .collect();
+
+    let mut succeeded = true;
+    for record in &records {
+        succeeded &= record.answer.is_ok();
+        match format {
+            Format::Text => println!("{record}"),
+            Format::Json => println!("{}", record.to_json()),
+        }
+    }
+
+    if all && format == Format::Text {
+        print!("\n{}", report::summary(&records, budget));
+    }
+
+    Ok(succeeded)
+}
+
+/// Makes sure `day`'s input is cached and in place for `aoc run`.
+fn fetch(day: u8) -> Result<bool> {
+    let config = Config::load()?;
+    let (cached, fetched) = fetch::fetch(&config, day)?;
+    match fetched {
+        fetch::Fetched::Downloaded => println!("day {day}: downloaded to {}", cached.display()),
+        fetch::Fetched::Cached => println!("day {day}: already cached at {}", cached.display()),
+    }
+
+    if let Input::File(target) = days::default_input(day) {
+        if fetch::install(&cached, &target)? {
+            println!("day {day}: copied to {}", target.display());
+        }
+    }
+    Ok(true)
+}
+
+/// Solves `part` of `day` and sends the answer, unless the history of past
+/// attempts says how that would go. Succeeds if the answer was right.
+fn submit(day: u8, part: u8) -> Result<bool> {
+    let solver = days::find(day).ok_or(format!("day {day} is not implemented"))?;
+    let source = days::default_input(day);
+    let contents = source.read()?;
+    let answer = solver
+        .solve(&contents, &[part])
+        .map_err(|err| locate(&source, err.as_ref()))?[0]
+        .to_string();
+
+    let config = Config::load()?;
+    let path = History::path(&config)?;
+    let mut history = History::load(&path)?;
+    let time = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)?
+        .as_secs();
+    history.check(day, part, &answer, time)?;
+
+    let (verdict, wait) = submit::submit(&config, day, part, &answer)?;
+    let attempt = submit::Attempt {
+        time,
+        day,
+        part,
+        verdict,
+        wait,
+        answer: answer.clone(),
+    };
+    history.record(&path, attempt)?;
+
+    print!("day {day} part {part}: {answer}: {verdict}");
+    if wait > 0 {
+        print!(" (wait {wait}s before answering again)");
+    }
+    println!();
+    Ok(verdict == submit::Verdict::Right)
+}
+
+/// Creates and registers a crate for `day`.
+fn new(day: u8) -> Result<bool> {
+    let workspace = days::workspace();
+    for path in scaffold::scaffold(workspace, day)? {
+        let path = path.strip_prefix(workspace).unwrap_or(&path);
+        println!("wrote {}", path.display());
+    }
+    println!("day {day} is ready: `aoc fetch {day}` gets its input");
+    Ok(true)
+}
+
+/// Sends the solvers' tracing to stderr, so answers on stdout stay clean.
+/// Without any `-v`s nothing is traced at all.
+fn explain(verbosity: u8) {
+    let level = match verbosity {
+        0 => return,
+        1 => LevelFilter::INFO,
+        2 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    };
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_max_level(level)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_target(false)
+        .with_timer(Uptime::default())
+        .init();
+}
+
+fn main() -> ExitCode {
+    let command = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("error: {err}\n{}", cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let result = match command {
+        Command::Run {
+            selection,
+            part,
+            input,
+            format,
+            jobs,
+            budget,
+            verbosity,
+        } => {
+            explain(verbosity);
+            run(selection, part, input, format, jobs, budget)
+        }
+        Command::Verify {
+            selection,
+            input,
+            answers,
+            record,
+        } => select(selection).and_then(|solvers| {
+            let answers = answers.unwrap_or_else(answers::default_path);
+            verify::verify(&solvers, input, &answers, record)
+        }),
+        Command::Fetch { day } => fetch(day),
+        Command::Submit { day, part } => submit(day, part),
+        Command::New { day } => new(day),
+        Command::Watch { day } => watch::watch(day),
+        Command::Gen {
+            day,
+            seed,
+            size,
+            answers,
+        } => gen::gen(day, seed, size, answers.as_deref()),
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
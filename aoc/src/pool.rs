@@ -0,0 +1,96 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// What a panic was raised with, if it was a message.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => payload
+            .downcast_ref::<String>()
+            .cloned()
+            .unwrap_or_else(|| "something unprintable".into()),
+    }
+}
+
+/// Applies `f` to every item on up to `jobs` threads, returning the results
+/// in the same order as `items`. Each thread takes the next unclaimed item as
+/// soon as it's free, so one slow day doesn't hold up a whole batch. If `f`
+/// panics on an item, its result is the panic's message, and the rest carry
+/// on without it.
+pub fn map<T, R>(items: &[T], jobs: usize, f: impl Fn(&T) -> R + Sync) -> Vec<Result<R, String>>
+where
+    T: Sync,
+    R: Send,
+{
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let sender = sender.clone();
+            let (next, f) = (&next, &f);
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(i) else {
+                    break;
+                };
+                let result = panic::catch_unwind(AssertUnwindSafe(|| f(item)));
+                sender
+                    .send((i, result.map_err(|payload| panic_message(payload.as_ref()))))
+                    .unwrap();
+            });
+        }
+    });
+    drop(sender);
+
+    let mut results: Vec<(usize, Result<R, String>)> = receiver.into_iter().collect();
+    results.sort_by_key(|&(i, _)| i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_map_keeps_order() {
+        let items: Vec<u64> = (0..20).collect();
+        let squares = map(&items, 4, |&n| {
+            // finish out of order
+            thread::sleep(Duration::from_millis(20 - n));
+            n * n
+        });
+        let expected: Vec<_> = items.iter().map(|n| Ok(n * n)).collect();
+        assert_eq!(squares, expected);
+    }
+
+    #[test]
+    fn test_map_job_counts() {
+        let items = [1, 2, 3];
+        assert_eq!(map(&items, 0, |n| n + 1), [Ok(2), Ok(3), Ok(4)]);
+        assert_eq!(map(&items, 16, |n| n + 1), [Ok(2), Ok(3), Ok(4)]);
+        assert_eq!(map(&[] as &[u8], 4, |n| *n), []);
+    }
+
+    #[test]
+    fn test_map_panics() {
+        let items = [1, 2, 3];
+        let results = map(&items, 2, |&n| {
+            assert!(n != 2, "no twos");
+            n
+        });
+        assert_eq!(results, [Ok(1), Err("no twos".into()), Ok(3)]);
+
+        let results = map(&items, 2, |&n| {
+            if n == 3 {
+                panic!("{n} is too many");
+            }
+            n
+        });
+        assert_eq!(results, [Ok(1), Ok(2), Err("3 is too many".into())]);
+    }
+}
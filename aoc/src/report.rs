@@ -0,0 +1,202 @@
+use aoc_common::{Answer, Run};
+use std::fmt::{self, Write};
+use std::time::Duration;
+
+/// The outcome of one part of one day, as the runner reports it.
+#[derive(Debug)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Result<Answer, String>,
+    pub parse_time: Option<Duration>,
+    pub solve_time: Option<Duration>,
+}
+
+impl Record {
+    /// One record per requested part of `run`. If parsing failed, every part
+    /// gets the parse error, already rendered by `describe`.
+    pub fn from_run(
+        day: u8,
+        parts: &[u8],
+        run: Run,
+        describe: impl Fn(&(dyn std::error::Error + 'static)) -> String,
+    ) -> Vec<Record> {
+        match run.parts {
+            Ok(solved) => solved
+                .into_iter()
+                .map(|solved| Record {
+                    day,
+                    part: solved.part,
+                    answer: solved.answer.map_err(|err| describe(err.as_ref())),
+                    parse_time: Some(run.parse_time),
+                    solve_time: Some(solved.solve_time),
+                })
+                .collect(),
+            Err(err) => Record::failed(day, parts, describe(&err), Some(run.parse_time)),
+        }
+    }
+
+    /// One record per part, all failing with `err`.
+    pub fn failed(day: u8, parts: &[u8], err: String, parse_time: Option<Duration>) -> Vec<Record> {
+        parts
+            .iter()
+            .map(|&part| Record {
+                day,
+                part,
+                answer: Err(err.clone()),
+                parse_time,
+                solve_time: None,
+            })
+            .collect()
+    }
+
+    /// The record as a single line of JSON. Answers are written as strings so
+    /// 64-bit values survive parsers that read every number as a double.
+    pub fn to_json(&self) -> String {
+        let (answer, kind, error) = match &self.answer {
+            Ok(answer) => (
+                json_string(&answer.to_string()),
+                json_string(answer.kind()),
+                "null".into(),
+            ),
+            Err(err) => ("null".into(), "null".into(), json_string(err)),
+        };
+        format!(
+            r#"{{"day":{},"part":{},"answer":{answer},"type":{kind},"parse_ns":{},"solve_ns":{},"error":{error}}}"#,
+            self.day,
+            self.part,
+            json_nanos(self.parse_time),
+            json_nanos(self.solve_time),
+        )
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.answer {
+            Ok(answer) => write!(f, "day {} part {}: {answer}", self.day, self.part),
+            Err(err) => write!(f, "day {} part {}: error: {err}", self.day, self.part),
+        }
+    }
+}
+
+/// A table of how long each part took, slowest first, flagging any that went
+/// over `budget`. Parts that failed before they could be timed are left out.
+pub fn summary(records: &[Record], budget: Duration) -> String {
+    let mut timed: Vec<(&Record, Duration, Duration)> = records
+        .iter()
+        .filter_map(|record| Some((record, record.parse_time?, record.solve_time?)))
+        .collect();
+    timed.sort_by_key(|&(record, parse, solve)| {
+        (std::cmp::Reverse(parse + solve), record.day, record.part)
+    });
+
+    let mut table = String::from("day part       parse       solve       total\n");
+    for (record, parse, solve) in timed {
+        let total = parse + solve;
+        write!(
+            table,
+            "{:>3} {:>4} {:>11} {:>11} {:>11}",
+            record.day,
+            record.part,
+            millis(parse),
+            millis(solve),
+            millis(total)
+        )
+        .unwrap();
+        if total > budget {
+            write!(table, "  over budget ({})", millis(budget)).unwrap();
+        }
+        table.push('\n');
+    }
+    table
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn json_nanos(duration: Option<Duration>) -> String {
+    duration.map_or("null".into(), |duration| duration.as_nanos().to_string())
+}
+
+fn json_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => write!(quoted, "\\u{:04x}", c as u32).unwrap(),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("LRL"), r#""LRL""#);
+        assert_eq!(
+            json_string("found '\"'\n\\\u{1}"),
+            r#""found '\"'\n\\\u0001""#
+        );
+    }
+
+    #[test]
+    fn test_summary() {
+        let record = |day, part, solve_ms| Record {
+            day,
+            part,
+            answer: Ok(Answer::Unsigned(0)),
+            parse_time: Some(Duration::from_millis(1)),
+            solve_time: Some(Duration::from_millis(solve_ms)),
+        };
+        let mut records = vec![record(4, 1, 2), record(5, 2, 1500), record(6, 2, 20)];
+        records.extend(Record::failed(8, &[1], "boom".into(), None));
+
+        assert_eq!(
+            summary(&records, Duration::from_secs(1)),
+            "\
+day part       parse       solve       total
+  5    2     1.000ms  1500.000ms  1501.000ms  over budget (1000.000ms)
+  6    2     1.000ms    20.000ms    21.000ms
+  4    1     1.000ms     2.000ms     3.000ms
+"
+        );
+    }
+
+    #[test]
+    fn test_to_json() {
+        let record = Record {
+            day: 7,
+            part: 2,
+            answer: Ok(Answer::Unsigned(5905)),
+            parse_time: Some(Duration::from_micros(12)),
+            solve_time: Some(Duration::from_nanos(3400)),
+        };
+        assert_eq!(
+            record.to_json(),
+            r#"{"day":7,"part":2,"answer":"5905","type":"unsigned","parse_ns":12000,"solve_ns":3400,"error":null}"#
+        );
+
+        let record = &Record::failed(
+            8,
+            &[1],
+            "input:1:3: expected 'L' or 'R', found 'X'".into(),
+            None,
+        )[0];
+        assert_eq!(
+            record.to_json(),
+            r#"{"day":8,"part":1,"answer":null,"type":null,"parse_ns":null,"solve_ns":null,"error":"input:1:3: expected 'L' or 'R', found 'X'"}"#
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use aoc_common::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const MANIFEST: &str = include_str!("../templates/day/Cargo.toml");
+const LIB: &str = include_str!("../templates/day/lib.rs");
+const EXAMPLE_ANSWERS: &str = "# part answer\n";
+
+/// Fills in a template for `day`, written as `{day}` or zero-padded as
+/// `{day:02}`.
+fn render(template: &str, day: u8) -> String {
+    template
+        .replace("{day:02}", &format!("{day:02}"))
+        .replace("{day}", &day.to_string())
+}
+
+/// The day a line registers, if it's one of the lines in a list of days that
+/// each start with `prefix` followed by the day number.
+fn registered_day(line: &str, prefix: &str) -> Option<u8> {
+    let rest = line.trim_start().strip_prefix(prefix)?;
+    let digits = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    rest[..digits].parse().ok()
+}
+
+/// Adds `entry` to the list of days in `s` whose lines start with `prefix`,
+/// keeping the list in day order and matching its indentation.
+fn register(s: &str, prefix: &str, day: u8, entry: &str) -> Result<String> {
+    let lines: Vec<&str> = s.lines().collect();
+    let days: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i, registered_day(line, prefix)?)))
+        .collect();
+
+    if days.iter().any(|&(_, registered)| registered == day) {
+        return Err(format!("day {day} is already registered").into());
+    }
+    let Some(&(first, _)) = days.first() else {
+        return Err(format!("couldn't find a list of days starting with '{prefix}'").into());
+    };
+    let at = days
+        .iter()
+        .rev()
+        .find(|&&(_, registered)| registered < day)
+        .map_or(first, |&(i, _)| i + 1);
+
+    let line = lines[first];
+    let indent = &line[..line.len() - line.trim_start().len()];
+    let mut registered: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+    registered.insert(at, format!("{indent}{entry}"));
+    Ok(registered.join("\n") + "\n")
+}
+
+/// Creates a crate for `day` in `workspace` and registers it with the
+/// workspace, the runner and its benchmarks, returning the files it wrote.
+/// It also starts the day's example unless there's one already. Every file
+/// is read, updated and written out beside where it's going before any are
+/// moved into place, so failing to read, register or write any of them
+/// leaves the workspace as it was.
+pub fn scaffold(workspace: &Path, day: u8) -> Result<Vec<PathBuf>> {
+    let dir = workspace.join(format!("day{day:02}"));
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()).into());
+    }
+
+    let registrations = [
+        ("Cargo.toml", "\"day", format!("\"day{day:02}\",")),
+        (
+            "aoc/Cargo.toml",
+            "day",
+            format!("day{day} = {{ path = \"../day{day:02}\" }}"),
+        ),
+        ("aoc/src/days.rs", "&day", format!("&day{day}::Day{day},")),
+        (
+            "aoc/benches/days.rs",
+            "bench_input::<day",
+            format!("bench_input::<day{day}::Day{day}>(c);"),
+        ),
+    ];
+    let mut files = vec![
+        (dir.join("Cargo.toml"), render(MANIFEST, day)),
+        (dir.join("src").join("lib.rs"), render(LIB, day)),
+    ];
+    // the crate's tests read the example, so it has to be there
+    let examples = workspace.join("examples").join(format!("day{day:02}"));
+    let had_examples = examples.exists();
+    for (name, contents) in [("example.txt", ""), ("example.answers", EXAMPLE_ANSWERS)] {
+        if !examples.join(name).exists() {
+            files.push((examples.join(name), contents.into()));
+        }
+    }
+    for (file, prefix, entry) in registrations {
+        let path = workspace.join(file);
+        let contents = fs::read_to_string(&path)
+            .map_err(|err| format!("{}: couldn't read: {err}", path.display()))?;
+        let registered = register(&contents, prefix, day, &entry)
+            .map_err(|err| format!("{}: {err}", path.display()))?;
+        files.push((path, registered));
+    }
+
+    // write everything beside where it's going first, so a failure part way
+    // through can be undone before anything is in place
+    let mut staged = Vec::new();
+    for (path, contents) in &files {
+        let stage = staging_path(path);
+        let written = fs::create_dir_all(path.parent().unwrap())
+            .and_then(|()| fs::write(&stage, contents))
+            .map_err(|err| format!("{}: couldn't write: {err}", path.display()));
+        if let Err(err) = written {
+            for stage in &staged {
+                let _ = fs::remove_file(stage);
+            }
+            let _ = fs::remove_dir_all(&dir);
+            if !had_examples {
+                let _ = fs::remove_dir_all(&examples);
+            }
+            return Err(err.into());
+        }
+        staged.push(stage);
+    }
+    for ((path, _), stage) in files.iter().zip(&staged) {
+        fs::rename(stage, path)
+            .map_err(|err| format!("{}: couldn't write: {err}", path.display()))?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+/// Where `path` is written before it's moved into place.
+fn staging_path(path: &Path) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!(".{name}.new"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_register() {
+        let members = "members = [\n    \"aoc\",\n    \"day01\",\n    \"day11\",\n]\n";
+        assert_eq!(
+            register(members, "\"day", 7, "\"day07\",").unwrap(),
+            "members = [\n    \"aoc\",\n    \"day01\",\n    \"day07\",\n    \"day11\",\n]\n"
+        );
+        assert_eq!(
+            register(members, "\"day", 12, "\"day12\",").unwrap(),
+            "members = [\n    \"aoc\",\n    \"day01\",\n    \"day11\",\n    \"day12\",\n]\n"
+        );
+
+        let days = "pub const DAYS: &[&dyn Solver] = &[\n    &day2::Day2,\n];\n";
+        assert_eq!(
+            register(days, "&day", 1, "&day1::Day1,").unwrap(),
+            "pub const DAYS: &[&dyn Solver] = &[\n    &day1::Day1,\n    &day2::Day2,\n];\n"
+        );
+    }
+
+    #[test]
+    fn test_register_errors() {
+        let days = "&[\n    &day1::Day1,\n    &day12::Day12,\n]\n";
+        assert_eq!(
+            register(days, "&day", 12, "&day12::Day12,")
+                .unwrap_err()
+                .to_string(),
+            "day 12 is already registered"
+        );
+        // day1 isn't mistaken for day12, or the other way round
+        assert!(register(days, "&day", 2, "&day2::Day2,").is_ok());
+        assert_eq!(
+            register("", "&day", 3, "&day3::Day3,")
+                .unwrap_err()
+                .to_string(),
+            "couldn't find a list of days starting with '&day'"
+        );
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let workspace = env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&workspace);
+        fs::create_dir_all(workspace.join("aoc/src")).unwrap();
+        fs::create_dir_all(workspace.join("aoc/benches")).unwrap();
+        let files = [
+            ("Cargo.toml", "members = [\n    \"day01\",\n]\n"),
+            (
+                "aoc/Cargo.toml",
+                "[dependencies]\nday1 = { path = \"../day01\" }\n",
+            ),
+            ("aoc/src/days.rs", "&[\n    &day1::Day1,\n];\n"),
+            (
+                "aoc/benches/days.rs",
+                "{\n    bench_input::<day1::Day1>(c);\n}\n",
+            ),
+        ];
+        for (file, contents) in files {
+            fs::write(workspace.join(file), contents).unwrap();
+        }
+
+        let written = scaffold(&workspace, 12).unwrap();
+        assert_eq!(written.len(), 8);
+        let read = |file| fs::read_to_string(workspace.join(file)).unwrap();
+        assert!(read("day12/Cargo.toml").contains("name = \"day12\"\n"));
+        assert!(
+            read("day12/src/lib.rs").contains("impl Solution for Day12 {\n    const DAY: u8 = 12;")
+        );
+        assert_eq!(
+            read("Cargo.toml"),
+            "members = [\n    \"day01\",\n    \"day12\",\n]\n"
+        );
+        assert!(read("aoc/Cargo.toml").ends_with("day12 = { path = \"../day12\" }\n"));
+        assert!(read("aoc/src/days.rs").contains("    &day12::Day12,\n"));
+        assert!(read("aoc/benches/days.rs").contains("    bench_input::<day12::Day12>(c);\n"));
+        assert!(read("day12/src/lib.rs").contains("\"../../examples/day12/example.txt\""));
+        assert_eq!(read("examples/day12/example.answers"), "# part answer\n");
+
+        // a second go leaves everything as it was
+        let err = scaffold(&workspace, 12).unwrap_err();
+        assert!(err.to_string().ends_with("day12 already exists"), "{err}");
+
+        // as does one that can't register the day
+        fs::write(workspace.join("aoc/src/days.rs"), "&[\n];\n").unwrap();
+        assert!(scaffold(&workspace, 13).is_err());
+        assert!(!workspace.join("day13").exists());
+        assert!(!workspace.join("examples/day13").exists());
+        assert!(!read("Cargo.toml").contains("day13"));
+
+        // and one that can't write everything out
+        fs::write(
+            workspace.join("aoc/src/days.rs"),
+            "&[\n    &day1::Day1,\n];\n",
+        )
+        .unwrap();
+        fs::remove_dir_all(workspace.join("examples")).unwrap();
+        fs::write(workspace.join("examples"), "").unwrap();
+        assert!(scaffold(&workspace, 13).is_err());
+        assert!(!workspace.join("day13").exists());
+        assert!(!read("Cargo.toml").contains("day13"));
+        assert!(!read("aoc/src/days.rs").contains("day13"));
+        let names: Vec<_> = fs::read_dir(&workspace)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert!(
+            !names
+                .iter()
+                .any(|name| name.to_string_lossy().ends_with(".new")),
+            "{names:?}"
+        );
+    }
+}
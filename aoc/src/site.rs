@@ -0,0 +1,74 @@
+use crate::config::Config;
+use aoc_common::Result;
+use std::error::Error;
+use std::time::Duration;
+
+const USER_AGENT: &str = concat!(
+    "aoc/",
+    env!("CARGO_PKG_VERSION"),
+    " (personal puzzle runner)"
+);
+
+fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .user_agent(USER_AGENT)
+        .timeout(Duration::from_secs(30))
+        .build()
+}
+
+/// Fetches `path` on the puzzle site as the configured user.
+pub fn get(config: &Config, path: &str) -> Result<String> {
+    let url = config.url(path);
+    let cookie = format!("session={}", config.session()?);
+    read(&url, agent().get(&url).set("Cookie", &cookie).call())
+}
+
+/// Posts `form` to `path` on the puzzle site as the configured user.
+pub fn post(config: &Config, path: &str, form: &[(&str, &str)]) -> Result<String> {
+    let url = config.url(path);
+    let cookie = format!("session={}", config.session()?);
+    read(
+        &url,
+        agent().post(&url).set("Cookie", &cookie).send_form(form),
+    )
+}
+
+/// The body of a response, or an error saying what went wrong in terms of
+/// what the user can do about it.
+fn read(url: &str, response: Result<ureq::Response, ureq::Error>) -> Result<String> {
+    match response {
+        Ok(response) => Ok(response
+            .into_string()
+            .map_err(|err| format!("couldn't read the response from {url}: {err}"))?),
+        Err(ureq::Error::Status(status, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            Err(status_error(url, status, &body).into())
+        }
+        Err(ureq::Error::Transport(err)) => {
+            let mut reason = err.kind().to_string();
+            if let Some(source) = err.source() {
+                reason += &format!(": {source}");
+            } else if let Some(message) = err.message() {
+                reason += &format!(": {message}");
+            }
+            Err(format!("couldn't reach {url}: {reason}").into())
+        }
+    }
+}
+
+fn status_error(url: &str, status: u16, body: &str) -> String {
+    // the site answers a stale session with a plea to log in rather than 401
+    if matches!(status, 400 | 401 | 403) || body.contains("log in") {
+        format!(
+            "the session token was rejected (HTTP {status}); it has probably expired, \
+             so log in again and update it"
+        )
+    } else if status == 404 {
+        format!("{url} wasn't found (HTTP 404); the puzzle may not be unlocked yet")
+    } else {
+        let reason = body.lines().next().unwrap_or_default().trim();
+        format!("{url} failed with HTTP {status}: {reason}")
+            .trim_end_matches([':', ' '])
+            .into()
+    }
+}
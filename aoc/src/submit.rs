@@ -0,0 +1,487 @@
+use crate::config::Config;
+use crate::site;
+use aoc_common::{parse_number, ParseError, Result};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+const HEADER: &str = "# time day part verdict wait answer\n";
+
+/// What the site made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Not checked, because the last answer was too recent.
+    Wait,
+    /// Not checked, because the part has already been solved.
+    Done,
+}
+
+impl Verdict {
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Right => "right",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Wait => "wait",
+            Verdict::Done => "done",
+        }
+    }
+
+    fn is_wrong(self) -> bool {
+        matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Verdict::Right,
+            Verdict::Wrong,
+            Verdict::TooHigh,
+            Verdict::TooLow,
+            Verdict::Wait,
+            Verdict::Done,
+        ]
+        .into_iter()
+        .find(|verdict| verdict.as_str() == s)
+        .ok_or(())
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Verdict::Right => "that's the right answer",
+            Verdict::Wrong => "that's not the right answer",
+            Verdict::TooHigh => "that's not the right answer; it's too high",
+            Verdict::TooLow => "that's not the right answer; it's too low",
+            Verdict::Wait => "not checked; the last answer was too recent",
+            Verdict::Done => "not checked; the site says this part is already solved",
+        })
+    }
+}
+
+/// The text of the site's reply, without its markup. Only the `<article>`
+/// holding the message matters if there is one.
+fn message_text(body: &str) -> String {
+    let article = match (body.find("<article"), body.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &body[start..end],
+        _ => body,
+    };
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => {
+                in_tag = false;
+                text.push(' ');
+            }
+            c if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// How many seconds `text` asks us to wait before answering again, from
+/// either "You have 1m 12s left to wait" or "please wait 5 minutes".
+fn wait_seconds(text: &str) -> u64 {
+    if let Some((before, _)) = text.split_once(" left to wait") {
+        let left = before.rsplit("You have ").next().unwrap_or_default();
+        return left
+            .split_whitespace()
+            .filter_map(|amount| {
+                let unit = match amount.chars().last()? {
+                    'h' => 3600,
+                    'm' => 60,
+                    's' => 1,
+                    _ => return None,
+                };
+                Some(amount[..amount.len() - 1].parse::<u64>().ok()? * unit)
+            })
+            .sum();
+    }
+
+    let Some((_, after)) = text.split_once("wait ") else {
+        return 0;
+    };
+    let mut words = after.split_whitespace();
+    let count = match words.next() {
+        Some("one" | "a") => 1,
+        Some(count) => count.parse().unwrap_or(0),
+        None => 0,
+    };
+    match words.next() {
+        Some(unit) if unit.starts_with("minute") => count * 60,
+        Some(unit) if unit.starts_with("second") => count,
+        _ => 0,
+    }
+}
+
+/// Reads the verdict and how long to wait before the next answer from the
+/// site's reply to a submission.
+pub fn parse_response(body: &str) -> Result<(Verdict, u64)> {
+    let text = message_text(body);
+    let verdict = if text.contains("That's the right answer") {
+        Verdict::Right
+    } else if text.contains("That's not the right answer") {
+        if text.contains("too high") {
+            Verdict::TooHigh
+        } else if text.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::Wait
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::Done
+    } else {
+        let excerpt: String = text.chars().take(200).collect();
+        return Err(format!("couldn't make sense of the site's reply: {excerpt:?}").into());
+    };
+
+    Ok((verdict, wait_seconds(&text)))
+}
+
+/// One answer we sent, and what came back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    pub day: u8,
+    pub part: u8,
+    pub verdict: Verdict,
+    /// Seconds the site asked us to wait before answering again.
+    pub wait: u64,
+    pub answer: String,
+}
+
+/// Every answer submitted so far, oldest first.
+///
+/// The file has one attempt per line, as `<time> <day> <part> <verdict>
+/// <wait> <answer>`, with blank lines and `#` comments ignored.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct History {
+    attempts: Vec<Attempt>,
+}
+
+impl FromStr for History {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut attempts = Vec::new();
+        for line in s.lines() {
+            let content = line.split('#').next().unwrap().trim();
+            if content.is_empty() {
+                continue;
+            }
+
+            let fields: Vec<&str> = content.split_whitespace().collect();
+            let &[time, day, part, verdict, wait, answer] = fields.as_slice() else {
+                return Err(ParseError::expected(
+                    s,
+                    content,
+                    "'<time> <day> <part> <verdict> <wait> <answer>'",
+                ));
+            };
+            attempts.push(Attempt {
+                time: parse_number(s, time)?,
+                day: parse_number(s, day)?,
+                part: parse_number(s, part)?,
+                verdict: verdict
+                    .parse()
+                    .map_err(|()| ParseError::invalid(s, verdict, "unknown verdict"))?,
+                wait: parse_number(s, wait)?,
+                answer: answer.into(),
+            });
+        }
+
+        Ok(History { attempts })
+    }
+}
+
+impl History {
+    /// The history file for the configured year, next to its cached inputs.
+    pub fn path(config: &Config) -> Result<PathBuf> {
+        Ok(config.cache()?.join("submissions"))
+    }
+
+    /// Reads the history at `path`, treating a missing file as empty.
+    pub fn load(path: &Path) -> Result<History> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ok(contents
+                .parse()
+                .map_err(|err| format!("{}:{err}", path.display()))?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(err) => Err(format!("{}: couldn't read history: {err}", path.display()).into()),
+        }
+    }
+
+    /// Records `attempt`, appending it to the file at `path`.
+    pub fn record(&mut self, path: &Path, attempt: Attempt) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        if file.metadata()?.len() == 0 {
+            file.write_all(HEADER.as_bytes())?;
+        }
+        let Attempt {
+            time,
+            day,
+            part,
+            verdict,
+            wait,
+            answer,
+        } = &attempt;
+        writeln!(
+            file,
+            "{time} {day} {part} {} {wait} {answer}",
+            verdict.as_str()
+        )?;
+
+        self.attempts.push(attempt);
+        Ok(())
+    }
+
+    /// Refuses to send `answer` for `day` and `part` at time `now` if the
+    /// history already says how it will go: the part is solved, the answer
+    /// or a smaller (or larger) one was already too high (or low), or the
+    /// site asked us to wait and the time isn't up yet.
+    pub fn check(&self, day: u8, part: u8, answer: &str, now: u64) -> Result<()> {
+        let attempts: Vec<&Attempt> = self
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.day == day && attempt.part == part)
+            .collect();
+
+        for attempt in &attempts {
+            match attempt.verdict {
+                Verdict::Right => {
+                    return Err(format!(
+                        "day {day} part {part} is already solved, with {}",
+                        attempt.answer
+                    )
+                    .into())
+                }
+                Verdict::Done => {
+                    return Err(
+                        format!("the site says day {day} part {part} is already solved").into(),
+                    )
+                }
+                verdict if verdict.is_wrong() && attempt.answer == answer => {
+                    return Err(format!("{answer} was already rejected: {verdict}").into())
+                }
+                _ => (),
+            }
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let bound = |verdict| {
+                attempts
+                    .iter()
+                    .filter(move |attempt| attempt.verdict == verdict)
+                    .filter_map(|attempt| attempt.answer.parse::<i128>().ok())
+            };
+            if let Some(high) = bound(Verdict::TooHigh).filter(|&high| value >= high).min() {
+                return Err(format!("{answer} is too high: {high} already was").into());
+            }
+            if let Some(low) = bound(Verdict::TooLow).filter(|&low| value <= low).max() {
+                return Err(format!("{answer} is too low: {low} already was").into());
+            }
+        }
+
+        let until = attempts
+            .iter()
+            .map(|attempt| attempt.time + attempt.wait)
+            .max();
+        if let Some(until) = until.filter(|&until| until > now) {
+            return Err(format!(
+                "the site asked us to wait before answering again; try in {}s",
+                until - now
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Sends `answer` for `day` and `part`, returning the site's verdict and how
+/// many seconds it wants before the next answer.
+pub fn submit(config: &Config, day: u8, part: u8, answer: &str) -> Result<(Verdict, u64)> {
+    let level = part.to_string();
+    let body = site::post(
+        config,
+        &format!("day/{day}/answer"),
+        &[("level", &level), ("answer", answer)],
+    )?;
+    parse_response(&body)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::Settings;
+    use crate::mock::Server;
+    use std::env;
+
+    const RIGHT: &str = "<html><body><main>\n<article><p>That's the right answer!  You are \
+        <span class=\"day-success\">one gold star</span> closer to restoring snow operations. \
+        <a href=\"/2023/day/7#part2\">[Continue to Part Two]</a></p></article>\n</main></body></html>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  \
+        If you're stuck, make sure you're using the full input data; there are also some general \
+        tips on the <a href=\"/2023/about\">about page</a>.  Please wait one minute before trying \
+        again. <a href=\"/2023/day/7\">[Return to Day 7]</a></p></article>";
+    const TOO_LOW: &str = "<article><p>That's not the right answer; your answer is too low.  \
+        Please wait 5 minutes before trying again.</p></article>";
+    const WRONG: &str = "<article><p>That's not the right answer.  If you're stuck, make sure \
+        you're using the full input data.  Please wait one minute before trying again.</p></article>";
+    const WAIT: &str = "<article><p>You gave an answer too recently; you have to wait after \
+        submitting an answer before trying again.  You have 1m 12s left to wait. \
+        <a href=\"/2023/day/7\">[Return to Day 7]</a></p></article>";
+    const DONE: &str = "<article><p>You don't seem to be solving the right level.  Did you \
+        already complete it? <a href=\"/2023/day/7\">[Return to Day 7]</a></p></article>";
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(parse_response(RIGHT).unwrap(), (Verdict::Right, 0));
+        assert_eq!(parse_response(TOO_HIGH).unwrap(), (Verdict::TooHigh, 60));
+        assert_eq!(parse_response(TOO_LOW).unwrap(), (Verdict::TooLow, 300));
+        assert_eq!(parse_response(WRONG).unwrap(), (Verdict::Wrong, 60));
+        assert_eq!(parse_response(WAIT).unwrap(), (Verdict::Wait, 72));
+        assert_eq!(parse_response(DONE).unwrap(), (Verdict::Done, 0));
+
+        let err = parse_response("<article><p>Gone fishing.</p></article>").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "couldn't make sense of the site's reply: \"Gone fishing.\""
+        );
+    }
+
+    fn attempt(time: u64, part: u8, verdict: Verdict, wait: u64, answer: &str) -> Attempt {
+        Attempt {
+            time,
+            day: 7,
+            part,
+            verdict,
+            wait,
+            answer: answer.into(),
+        }
+    }
+
+    #[test]
+    fn test_parse_history() {
+        let history: History = "\
+# time day part verdict wait answer
+1701950400 7 1 too-high 60 6500
+
+1701950500 7 1 right 0 6440  # second time lucky
+"
+        .parse()
+        .unwrap();
+        assert_eq!(
+            history.attempts,
+            [
+                attempt(1701950400, 1, Verdict::TooHigh, 60, "6500"),
+                attempt(1701950500, 1, Verdict::Right, 0, "6440"),
+            ]
+        );
+
+        let err = "1701950400 7 1 right 6440\n"
+            .parse::<History>()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "1:1: expected '<time> <day> <part> <verdict> <wait> <answer>', \
+             found '1701950400 7 1 right 6440'"
+        );
+        let err = "1701950400 7 1 close 0 6440\n"
+            .parse::<History>()
+            .unwrap_err();
+        assert_eq!(err.to_string(), "1:16: unknown verdict: 'close'");
+    }
+
+    #[test]
+    fn test_check() {
+        let history = History {
+            attempts: vec![
+                attempt(1000, 1, Verdict::TooHigh, 60, "6500"),
+                attempt(1100, 1, Verdict::TooLow, 300, "6000"),
+                attempt(1500, 1, Verdict::Wrong, 60, "6400"),
+                attempt(1600, 2, Verdict::Right, 0, "5905"),
+            ],
+        };
+        let check = |part, answer, now| {
+            history
+                .check(7, part, answer, now)
+                .map_err(|err| err.to_string())
+        };
+
+        assert_eq!(check(1, "6440", 2000), Ok(()));
+        assert_eq!(
+            check(1, "6400", 2000),
+            Err("6400 was already rejected: that's not the right answer".into())
+        );
+        assert_eq!(
+            check(1, "6501", 2000),
+            Err("6501 is too high: 6500 already was".into())
+        );
+        assert_eq!(
+            check(1, "5999", 2000),
+            Err("5999 is too low: 6000 already was".into())
+        );
+        assert_eq!(
+            check(1, "6440", 1530),
+            Err("the site asked us to wait before answering again; try in 30s".into())
+        );
+        assert_eq!(
+            check(2, "5905", 2000),
+            Err("day 7 part 2 is already solved, with 5905".into())
+        );
+        // other days aren't affected, and words aren't bounded by numbers
+        assert_eq!(history.check(8, 1, "6501", 1530).ok(), Some(()));
+        assert_eq!(check(1, "ABCDEF", 2000), Ok(()));
+    }
+
+    #[test]
+    fn test_submit() {
+        let server = Server::start(vec![(200, TOO_HIGH)]);
+        let cache = env::temp_dir().join(format!("aoc-submit-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&cache);
+        let config = Config::resolve(Settings::default(), None, |var| match var {
+            "AOC_SESSION" => Some("cafe".into()),
+            "AOC_BASE_URL" => Some(server.url().into()),
+            "AOC_CACHE_DIR" => Some(cache.to_string_lossy().into()),
+            _ => None,
+        });
+
+        assert_eq!(
+            submit(&config, 7, 1, "6500").unwrap(),
+            (Verdict::TooHigh, 60)
+        );
+        let requests = server.requests();
+        assert!(requests[0].starts_with("POST /2023/day/7/answer HTTP/1.1\r\n"));
+        assert!(requests[0].contains("\r\nCookie: session=cafe\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=1&answer=6500"));
+
+        // recorded attempts survive a reload
+        let path = History::path(&config).unwrap();
+        let mut history = History::load(&path).unwrap();
+        let attempt = attempt(1000, 1, Verdict::TooHigh, 60, "6500");
+        history.record(&path, attempt.clone()).unwrap();
+        assert_eq!(History::load(&path).unwrap().attempts, [attempt]);
+    }
+}
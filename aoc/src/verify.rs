@@ -0,0 +1,179 @@
+use crate::answers::{input_hash, Answers};
+use crate::days;
+use crate::locate;
+use crate::report::Record;
+use aoc_common::{input, Input, Result, Solver};
+use std::fmt;
+use std::path::Path;
+
+/// How one part's answer compares with the one we recorded.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String },
+    Unrecorded,
+    Recorded,
+    Error(String),
+    NoInput,
+}
+
+impl Status {
+    pub fn check(recorded: Option<&str>, answer: &str) -> Status {
+        match recorded {
+            Some(expected) if expected == answer => Status::Pass,
+            Some(expected) => Status::Fail {
+                expected: expected.into(),
+            },
+            None => Status::Unrecorded,
+        }
+    }
+
+    pub fn is_regression(&self) -> bool {
+        matches!(self, Status::Fail { .. } | Status::Error(_))
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Status::Pass => "pass",
+            Status::Fail { .. } => "FAIL",
+            Status::Unrecorded => "unrecorded",
+            Status::Recorded => "recorded",
+            Status::Error(_) => "ERROR",
+            Status::NoInput => "no input",
+        };
+        // pad here so the table lines up however the label is formatted
+        f.pad(label)
+    }
+}
+
+struct Row {
+    day: u8,
+    part: Option<u8>,
+    status: Status,
+    answer: Option<String>,
+}
+
+impl fmt::Display for Row {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let part = self.part.map_or("-".into(), |part| part.to_string());
+        let mut line = format!("{:>3} {:>4}  {:<10}", self.day, part, self.status);
+        if let Some(answer) = &self.answer {
+            line += &format!("  {answer}");
+        }
+        match &self.status {
+            Status::Fail { expected } => line += &format!(" (expected {expected})"),
+            Status::Error(err) => line += &format!("  {err}"),
+            _ => (),
+        }
+        f.write_str(line.trim_end())
+    }
+}
+
+/// Checks each of `solvers` against the answers recorded at `path`, printing
+/// a table as it goes. Returns whether every recorded answer still holds.
+pub fn verify(
+    solvers: &[&dyn Solver],
+    input: Option<Input>,
+    path: &Path,
+    record: bool,
+) -> Result<bool> {
+    let mut answers = Answers::load(path)?;
+    let mut regressions = 0;
+
+    println!("day part  status      answer");
+    for solver in solvers {
+        let day = solver.day();
+        let source = match &input {
+            Some(input) => input.clone(),
+            None => days::default_input(day),
+        };
+        if matches!(&source, Input::File(path) if input.is_none() && !path.exists()) {
+            let status = Status::NoInput;
+            let row = Row {
+                day,
+                part: None,
+                status,
+                answer: None,
+            };
+            println!("{row}");
+            continue;
+        }
+
+        let contents = source.read()?;
+        // the same input saved with different line endings is still the same
+        let hash = input_hash(&input::normalise(&contents));
+        let parts = [1, 2];
+        let run = solver.run(&contents, &parts);
+        for solved in Record::from_run(day, &parts, run, |err| locate(&source, err)) {
+            let part = solved.part;
+            let (status, answer) = match solved.answer {
+                Ok(answer) => {
+                    let answer = answer.to_string();
+                    let status = match Status::check(answers.get(day, part, hash), &answer) {
+                        Status::Unrecorded if record => {
+                            answers.record(path, day, part, hash, &answer)?;
+                            Status::Recorded
+                        }
+                        status => status,
+                    };
+                    (status, Some(answer))
+                }
+                Err(err) => (Status::Error(err), None),
+            };
+
+            if status.is_regression() {
+                regressions += 1;
+            }
+            let part = Some(part);
+            let row = Row {
+                day,
+                part,
+                status,
+                answer,
+            };
+            println!("{row}");
+        }
+    }
+
+    if regressions > 0 {
+        println!("\n{regressions} regression(s) against {}", path.display());
+    }
+    Ok(regressions == 0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert_eq!(Status::check(Some("6440"), "6440"), Status::Pass);
+        assert_eq!(
+            Status::check(Some("6440"), "6441"),
+            Status::Fail {
+                expected: "6440".into()
+            }
+        );
+        assert_eq!(Status::check(None, "6440"), Status::Unrecorded);
+        assert!(!Status::Unrecorded.is_regression());
+        assert!(Status::Error("boom".into()).is_regression());
+    }
+
+    #[test]
+    fn test_row() {
+        let row = Row {
+            day: 7,
+            part: Some(2),
+            status: Status::Fail {
+                expected: "5905".into(),
+            },
+            answer: Some("5906".into()),
+        };
+        assert_eq!(
+            row.to_string(),
+            "  7    2  FAIL        5906 (expected 5905)"
+        );
+    }
+}
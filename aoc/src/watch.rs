@@ -0,0 +1,359 @@
+use crate::days;
+use aoc_common::Result;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+const POLL: Duration = Duration::from_millis(500);
+
+/// When each watched file was last modified, or `None` if it's missing.
+type Snapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+/// Records the modification times of every file under `dirs`, and of `files`
+/// whether or not they exist yet.
+fn snapshot(dirs: &[PathBuf], files: &[PathBuf]) -> Snapshot {
+    fn walk(dir: &Path, snapshot: &mut Snapshot) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            match entry.metadata() {
+                Ok(metadata) if metadata.is_dir() => walk(&path, snapshot),
+                Ok(metadata) => {
+                    snapshot.insert(path, metadata.modified().ok());
+                }
+                Err(_) => (),
+            }
+        }
+    }
+
+    let mut snapshot = Snapshot::new();
+    for dir in dirs {
+        walk(dir, &mut snapshot);
+    }
+    for file in files {
+        let modified = fs::metadata(file).and_then(|metadata| metadata.modified());
+        snapshot.insert(file.clone(), modified.ok());
+    }
+    snapshot
+}
+
+/// The files that were added, removed or modified between two snapshots.
+fn changes<'a>(before: &'a Snapshot, after: &'a Snapshot) -> Vec<&'a Path> {
+    let mut changed: Vec<&Path> = after
+        .iter()
+        .filter(|&(path, modified)| before.get(path) != Some(modified))
+        .map(|(path, _)| path.as_path())
+        .collect();
+    changed.extend(
+        before
+            .keys()
+            .filter(|path| !after.contains_key(*path))
+            .map(PathBuf::as_path),
+    );
+    changed
+}
+
+/// One part's result, as read back from `aoc run --format json`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Outcome {
+    part: u8,
+    answer: Result<String, String>,
+    time: Option<Duration>,
+}
+
+/// Reads the values of a flat JSON object like the ones `Record::to_json`
+/// writes, with `null` read as `None`. Anything else gives `None`.
+fn json_fields(line: &str) -> Option<BTreeMap<String, Option<String>>> {
+    fn string(chars: &mut std::iter::Peekable<std::str::Chars>) -> Option<String> {
+        let mut s = String::new();
+        loop {
+            match chars.next()? {
+                '"' => return Some(s),
+                '\\' => match chars.next()? {
+                    'n' => s.push('\n'),
+                    'r' => s.push('\r'),
+                    't' => s.push('\t'),
+                    'u' => {
+                        let code: String = chars.by_ref().take(4).collect();
+                        s.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+                    }
+                    c => s.push(c),
+                },
+                c => s.push(c),
+            }
+        }
+    }
+
+    let mut chars = line.trim().strip_prefix('{')?.chars().peekable();
+    let mut fields = BTreeMap::new();
+    loop {
+        match chars.next()? {
+            '}' => return Some(fields),
+            ',' => continue,
+            '"' => (),
+            _ => return None,
+        }
+        let key = string(&mut chars)?;
+        if chars.next()? != ':' {
+            return None;
+        }
+        let value = if chars.peek() == Some(&'"') {
+            chars.next();
+            Some(string(&mut chars)?)
+        } else {
+            let mut raw = String::new();
+            while let Some(&c) = chars.peek() {
+                if c == ',' || c == '}' {
+                    break;
+                }
+                raw.push(c);
+                chars.next();
+            }
+            (raw != "null").then_some(raw)
+        };
+        fields.insert(key, value);
+    }
+}
+
+impl Outcome {
+    fn from_json(line: &str) -> Option<Outcome> {
+        let fields = json_fields(line)?;
+        let field = |name: &str| fields.get(name).cloned().flatten();
+        let nanos = |name: &str| field(name)?.parse().ok().map(Duration::from_nanos);
+
+        let answer = match (field("answer"), field("error")) {
+            (Some(answer), _) => Ok(answer),
+            (None, error) => Err(error.unwrap_or_default()),
+        };
+        let time = nanos("parse_ns").zip(nanos("solve_ns"));
+        Some(Outcome {
+            part: field("part")?.parse().ok()?,
+            answer,
+            time: time.map(|(parse, solve)| parse + solve),
+        })
+    }
+
+    /// The outcome for the user, compared with the `previous` one.
+    fn describe(&self, day: u8, previous: Option<&Outcome>) -> String {
+        let mut line = format!("day {day} part {}: ", self.part);
+        match (&self.answer, previous.map(|previous| &previous.answer)) {
+            (Ok(answer), Some(Ok(before))) if answer == before => {
+                line += &format!("{answer} (unchanged)")
+            }
+            (Ok(answer), Some(Ok(before))) => line += &format!("{answer} (was {before})"),
+            (Ok(answer), _) => line += answer,
+            (Err(err), _) => line += &format!("error: {err}"),
+        }
+
+        if let Some(time) = self.time {
+            line += &format!(" in {}", millis(time));
+            if let Some(before) = previous.and_then(|previous| previous.time) {
+                let change = (time.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0;
+                line += &format!(" (was {}, {change:+.0}%)", millis(before));
+            }
+        }
+        line
+    }
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn cargo() -> Command {
+    let mut command = Command::new(env::var_os("CARGO").unwrap_or("cargo".into()));
+    command.current_dir(days::workspace());
+    command
+}
+
+/// Runs the day's tests and examples, then its solver if they pass, printing
+/// how the answers compare with `previous` ones. Returns the new answers, or
+/// the previous ones if the tests failed and there was nothing to compare.
+fn cycle(day: u8, previous: Vec<Outcome>) -> Result<Vec<Outcome>> {
+    let start = Instant::now();
+    let tests = cargo()
+        .args(["test", "-q", "-p", &format!("day{day}")])
+        .status()?;
+    // the examples are checked by a harness in this crate, which can be
+    // told to check only the one day
+    let examples = || {
+        cargo()
+            .args(["test", "-q", "-p", "aoc", "--bin", "aoc", "examples::"])
+            .env("AOC_EXAMPLES_DAY", day.to_string())
+            .status()
+    };
+    if !tests.success() || !examples()?.success() {
+        println!("tests failed, so not solving");
+        return Ok(previous);
+    }
+    println!("tests passed in {:.1}s", start.elapsed().as_secs_f64());
+
+    let output = cargo()
+        .args(["run", "-q", "--release", "-p", "aoc", "--"])
+        .args(["run", &day.to_string(), "--format", "json"])
+        .stderr(Stdio::inherit())
+        .output()?;
+    let outcomes: Vec<Outcome> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(Outcome::from_json)
+        .collect();
+    for outcome in &outcomes {
+        let before = previous.iter().find(|before| before.part == outcome.part);
+        println!("{}", outcome.describe(day, before));
+    }
+    Ok(outcomes)
+}
+
+/// Re-runs `day`'s tests and solver whenever anything in its `src` directory,
+/// its examples or its input changes, until interrupted.
+pub fn watch(day: u8) -> Result<bool> {
+    let dir = days::workspace().join(format!("day{day:02}"));
+    if !dir.is_dir() {
+        return Err(format!(
+            "{} doesn't exist; create it with `aoc new {day}`",
+            dir.display()
+        )
+        .into());
+    }
+    let examples = days::workspace()
+        .join("examples")
+        .join(format!("day{day:02}"));
+    let dirs = [dir.join("src"), examples];
+    let files = [dir.join("input")];
+
+    let watched: Vec<String> = dirs
+        .iter()
+        .chain(&files)
+        .map(|path| path.display().to_string())
+        .collect();
+    println!("watching {}", watched.join(", "));
+    let mut seen = snapshot(&dirs, &files);
+    let mut outcomes = cycle(day, Vec::new())?;
+    loop {
+        thread::sleep(POLL);
+        let current = snapshot(&dirs, &files);
+        if current == seen {
+            continue;
+        }
+
+        // editors often save in several steps, so wait for things to settle
+        let mut settled = current;
+        loop {
+            thread::sleep(POLL);
+            let next = snapshot(&dirs, &files);
+            if next == settled {
+                break;
+            }
+            settled = next;
+        }
+
+        let workspace = days::workspace();
+        let changed: Vec<String> = changes(&seen, &settled)
+            .into_iter()
+            .map(|path| {
+                path.strip_prefix(workspace)
+                    .unwrap_or(path)
+                    .display()
+                    .to_string()
+            })
+            .collect();
+        println!("\n--- {} changed", changed.join(", "));
+        seen = settled;
+        outcomes = cycle(day, outcomes)?;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::report::Record;
+    use aoc_common::Answer;
+
+    #[test]
+    fn test_snapshot() {
+        let dir = env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src/bin")).unwrap();
+        fs::write(dir.join("src/lib.rs"), "").unwrap();
+        fs::write(dir.join("src/bin/main.rs"), "").unwrap();
+        let dirs = [dir.join("src")];
+        let files = [dir.join("input")];
+
+        let before = snapshot(&dirs, &files);
+        assert_eq!(before.len(), 3);
+        assert_eq!(before[&dir.join("input")], None);
+        assert!(changes(&before, &snapshot(&dirs, &files)).is_empty());
+
+        fs::write(dir.join("input"), "32T3K 765\n").unwrap();
+        fs::remove_file(dir.join("src/bin/main.rs")).unwrap();
+        let after = snapshot(&dirs, &files);
+        assert_eq!(
+            changes(&before, &after),
+            [dir.join("input"), dir.join("src/bin/main.rs")]
+        );
+    }
+
+    #[test]
+    fn test_from_json() {
+        let record = Record {
+            day: 7,
+            part: 2,
+            answer: Ok(Answer::Unsigned(5905)),
+            parse_time: Some(Duration::from_micros(12)),
+            solve_time: Some(Duration::from_micros(30)),
+        };
+        assert_eq!(
+            Outcome::from_json(&record.to_json()),
+            Some(Outcome {
+                part: 2,
+                answer: Ok("5905".into()),
+                time: Some(Duration::from_micros(42)),
+            })
+        );
+
+        let record = &Record::failed(8, &[1], "input:1:3: found '\"'\n".into(), None)[0];
+        assert_eq!(
+            Outcome::from_json(&record.to_json()),
+            Some(Outcome {
+                part: 1,
+                answer: Err("input:1:3: found '\"'\n".into()),
+                time: None,
+            })
+        );
+
+        assert_eq!(Outcome::from_json("day 7 part 1: 6440"), None);
+    }
+
+    #[test]
+    fn test_describe() {
+        let outcome = |answer: &str, micros| Outcome {
+            part: 1,
+            answer: Ok(answer.into()),
+            time: Some(Duration::from_micros(micros)),
+        };
+        let now = outcome("6440", 150);
+
+        assert_eq!(now.describe(7, None), "day 7 part 1: 6440 in 0.150ms");
+        assert_eq!(
+            now.describe(7, Some(&outcome("6440", 200))),
+            "day 7 part 1: 6440 (unchanged) in 0.150ms (was 0.200ms, -25%)"
+        );
+        assert_eq!(
+            now.describe(7, Some(&outcome("6441", 100))),
+            "day 7 part 1: 6440 (was 6441) in 0.150ms (was 0.100ms, +50%)"
+        );
+
+        let failed = Outcome {
+            part: 1,
+            answer: Err("boom".into()),
+            time: None,
+        };
+        assert_eq!(failed.describe(7, Some(&now)), "day 7 part 1: error: boom");
+    }
+}
@@ -0,0 +1,37 @@
+use aoc_common::{Answer, ParseError, Parser, Result, Solution};
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    const DAY: u8 = {day};
+
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Ok(Parser::new(input)
+            .lines()
+            .map(|line| line.rest().to_string())
+            .collect())
+    }
+
+    fn part1(_lines: &Self::Parsed) -> Result<Answer> {
+        Err("part 1 isn't solved yet".into())
+    }
+
+    fn part2(_lines: &Self::Parsed) -> Result<Answer> {
+        Err("part 2 isn't solved yet".into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TEST: &str = include_str!("../../examples/day{day:02}/example.txt");
+
+    #[test]
+    fn test_parse() {
+        let lines = Day{day}::parse(TEST).unwrap();
+        assert_eq!(lines.len(), TEST.lines().count());
+    }
+}
@@ -0,0 +1,134 @@
+use aoc_common::{Answer, ParseError, ParseErrorKind, Parser, Result, Solution};
+use tracing::trace;
+
+const PATTERNS: [(&str, u32); 18] = [
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+// part 1 only counts numerals, part 2 also counts spelled out digits
+const DIGITS: &[(&str, u32)] = PATTERNS.split_at(9).0;
+const SPELLED_DIGITS: &[(&str, u32)] = &PATTERNS;
+
+fn parse_digits(calibration: &str, patterns: &[(&str, u32)]) -> Option<u32> {
+    let mut first_digit_index = calibration.len();
+    let mut first_digit = 0;
+    for (pattern, digit) in patterns {
+        if let Some(index) = calibration.find(pattern) {
+            if index < first_digit_index {
+                first_digit_index = index;
+                first_digit = *digit;
+            }
+        }
+    }
+    if first_digit == 0 {
+        return None;
+    }
+
+    let mut second_digit_index: i64 = -1;
+    let mut second_digit = 0;
+    for (pattern, digit) in patterns {
+        if let Some(index) = calibration.rfind(pattern) {
+            if index as i64 > second_digit_index {
+                second_digit_index = index as i64;
+                second_digit = *digit;
+            }
+        }
+    }
+
+    Some(first_digit * 10 + second_digit)
+}
+
+fn sum_calibrations(calibrations: &[String], patterns: &[(&str, u32)]) -> Result<u32> {
+    let mut sum = 0;
+    for (i, calibration) in calibrations.iter().enumerate() {
+        let value = parse_digits(calibration, patterns).ok_or_else(|| ParseError {
+            line: i + 1,
+            column: 1,
+            kind: ParseErrorKind::Expected {
+                expected: "a digit".into(),
+                found: calibration.clone(),
+            },
+        })?;
+        trace!(line = i + 1, calibration, value);
+        sum += value;
+    }
+
+    Ok(sum)
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Ok(Parser::new(input)
+            .lines()
+            .map(|line| line.rest().to_string())
+            .collect())
+    }
+
+    fn part1(calibrations: &Self::Parsed) -> Result<Answer> {
+        Ok(sum_calibrations(calibrations, DIGITS)?.into())
+    }
+
+    fn part2(calibrations: &Self::Parsed) -> Result<Answer> {
+        Ok(sum_calibrations(calibrations, SPELLED_DIGITS)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_numeral_parsing() {
+        assert_eq!(parse_digits("1abc2", DIGITS), Some(12));
+        assert_eq!(parse_digits("pqr3stu8vwx", DIGITS), Some(38));
+        assert_eq!(parse_digits("a1b2c3d4e5f", DIGITS), Some(15));
+        assert_eq!(parse_digits("treb7uchet", DIGITS), Some(77));
+        assert_eq!(parse_digits("two1nine", DIGITS), Some(11));
+    }
+
+    #[test]
+    fn check_digit_parsing() {
+        assert_eq!(parse_digits("two1nine", SPELLED_DIGITS), Some(29));
+        assert_eq!(parse_digits("eightwothree", SPELLED_DIGITS), Some(83));
+        assert_eq!(parse_digits("abcone2threexyz", SPELLED_DIGITS), Some(13));
+        assert_eq!(parse_digits("xtwone3four", SPELLED_DIGITS), Some(24));
+        assert_eq!(parse_digits("4nineeightseven2", SPELLED_DIGITS), Some(42));
+        assert_eq!(parse_digits("zoneight234", SPELLED_DIGITS), Some(14));
+        assert_eq!(parse_digits("7pqrstsixteen", SPELLED_DIGITS), Some(76));
+        assert_eq!(parse_digits("twone", SPELLED_DIGITS), Some(21));
+        assert_eq!(parse_digits("7qpnldcvgs", SPELLED_DIGITS), Some(77));
+    }
+
+    #[test]
+    fn check_missing_digits() {
+        assert_eq!(parse_digits("eightwothree", DIGITS), None);
+
+        let calibrations = Day1::parse("1abc2\nnine\n").unwrap();
+        let err = Day1::part1(&calibrations).unwrap_err();
+        assert_eq!(err.to_string(), "2:1: expected a digit, found 'nine'");
+        assert_eq!(Day1::part2(&calibrations).unwrap(), 12 + 99);
+    }
+}
@@ -0,0 +1,181 @@
+use aoc_common::{Answer, ParseError, Parser, Result, Solution};
+use std::cmp;
+use tracing::trace;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Cubes {
+    red: u32,
+    green: u32,
+    blue: u32,
+}
+
+impl Cubes {
+    // a comma separated list of '<count> <color>'
+    fn parse(p: &mut Parser) -> Result<Cubes, ParseError> {
+        let mut cubes = Cubes::default();
+        p.separated(",", |p| {
+            p.spaces();
+            let count = p.number()?;
+            p.literal(" ")?;
+            let color = p.word()?;
+            match color {
+                "red" => cubes.red = count,
+                "green" => cubes.green = count,
+                "blue" => cubes.blue = count,
+                _ => return Err(p.expected(color, "'red', 'green' or 'blue'")),
+            };
+            Ok(())
+        })?;
+
+        Ok(cubes)
+    }
+}
+
+#[derive(Debug)]
+pub struct Game {
+    id: u32,
+    draws: Vec<Cubes>,
+}
+
+impl Game {
+    fn parse(p: &mut Parser) -> Result<Game, ParseError> {
+        p.literal("Game ")?;
+        let id = p.number()?;
+        p.literal(":")?;
+        let draws = p.separated(";", Cubes::parse)?;
+
+        Ok(Game { id, draws })
+    }
+
+    fn minimum_cubes(&self) -> Cubes {
+        self.draws.iter().fold(Cubes::default(), |min, draw| Cubes {
+            red: cmp::max(min.red, draw.red),
+            green: cmp::max(min.green, draw.green),
+            blue: cmp::max(min.blue, draw.blue),
+        })
+    }
+
+    fn is_possible_with(&self, bag: &Cubes) -> bool {
+        let min = self.minimum_cubes();
+        min.red <= bag.red && min.green <= bag.green && min.blue <= bag.blue
+    }
+
+    fn power(&self) -> u32 {
+        let min = self.minimum_cubes();
+        min.red * min.green * min.blue
+    }
+}
+
+const BAG: Cubes = Cubes {
+    red: 12,
+    green: 13,
+    blue: 14,
+};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Parsed = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Parser::new(input).each_line(Game::parse)
+    }
+
+    fn part1(games: &Self::Parsed) -> Result<Answer> {
+        Ok(games
+            .iter()
+            .filter(|game| {
+                let possible = game.is_possible_with(&BAG);
+                trace!(game = game.id, possible);
+                possible
+            })
+            .map(|game| game.id)
+            .sum::<u32>()
+            .into())
+    }
+
+    fn part2(games: &Self::Parsed) -> Result<Answer> {
+        Ok(games
+            .iter()
+            .map(|game| {
+                let power = game.power();
+                trace!(game = game.id, power);
+                power
+            })
+            .sum::<u32>()
+            .into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn calculate_power(record: &str) -> u32 {
+        Game::parse(&mut Parser::new(record)).unwrap().power()
+    }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(
+            calculate_power("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"),
+            48
+        );
+        assert_eq!(
+            calculate_power("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue"),
+            12
+        );
+        assert_eq!(
+            calculate_power(
+                "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red"
+            ),
+            1560
+        );
+        assert_eq!(
+            calculate_power(
+                "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red"
+            ),
+            630
+        );
+        assert_eq!(
+            calculate_power("Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"),
+            36
+        );
+    }
+
+    const TEST: &str = include_str!("../../examples/day02/example.txt");
+
+    #[test]
+    fn test_possible_games() {
+        let games = Day2::parse(TEST).unwrap();
+        assert_eq!(games[0].id, 1);
+        assert_eq!(
+            games[0].draws[1],
+            Cubes {
+                red: 1,
+                green: 2,
+                blue: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day2::parse("Game 1: 3 blue\nGame 2: 1 blue, 2 purple\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "2:19: expected 'red', 'green' or 'blue', found 'purple'"
+        );
+
+        let err = Day2::parse("Game x: 3 blue").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "1:6: invalid number 'x': invalid digit found in string"
+        );
+
+        let err = Day2::parse("Game 1 3 blue").unwrap_err();
+        assert_eq!(err.to_string(), "1:8: expected ':', found '3 blue'");
+    }
+}
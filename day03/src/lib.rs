@@ -0,0 +1,214 @@
+use aoc_common::{Answer, Grid, ParseError, Position, Result, Solution};
+use std::str::FromStr;
+use tracing::trace;
+
+#[derive(Debug, PartialEq, Eq)]
+struct PartNumber {
+    value: u32,
+    row: usize,
+    start: usize,
+    end: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Symbol {
+    symbol: char,
+    row: usize,
+    column: usize,
+}
+
+impl PartNumber {
+    fn is_adjacent_to(&self, symbol: &Symbol) -> bool {
+        symbol.row + 1 >= self.row
+            && symbol.row <= self.row + 1
+            && symbol.column + 1 >= self.start
+            && symbol.column <= self.end + 1
+    }
+}
+
+pub struct Schematic {
+    numbers: Vec<PartNumber>,
+    symbols: Vec<Symbol>,
+}
+
+// the run of digits at `position`, as part of `s` to point an error at
+fn digit_run(s: &str, (row, column): Position) -> &str {
+    let line = s.lines().nth(row).unwrap_or_default();
+    let start = line
+        .char_indices()
+        .nth(column)
+        .map_or(line.len(), |(i, _)| i);
+    let run = &line[start..];
+    &run[..run.find(|c: char| !c.is_ascii_digit()).unwrap_or(run.len())]
+}
+
+impl FromStr for Schematic {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // anything other than a digit or '.' is a symbol
+        let grid = Grid::parse(s, Ok::<char, String>)?;
+        let mut numbers = Vec::new();
+        let mut symbols = Vec::new();
+
+        for (row, cells) in grid.rows().enumerate() {
+            let mut digits: Option<(usize, u32)> = None;
+            for (column, &c) in cells.iter().chain(&['.']).enumerate() {
+                if let Some(digit) = c.to_digit(10) {
+                    let (start, value) = digits.unwrap_or((column, 0));
+                    let value = value
+                        .checked_mul(10)
+                        .and_then(|value| value.checked_add(digit))
+                        .ok_or_else(|| {
+                            ParseError::invalid(s, digit_run(s, (row, start)), "number too large")
+                        })?;
+                    digits = Some((start, value));
+                    continue;
+                }
+
+                if let Some((start, value)) = digits.take() {
+                    numbers.push(PartNumber {
+                        value,
+                        row,
+                        start,
+                        end: column - 1,
+                    });
+                }
+
+                if c != '.' {
+                    symbols.push(Symbol {
+                        symbol: c,
+                        row,
+                        column,
+                    });
+                }
+            }
+        }
+
+        Ok(Schematic { numbers, symbols })
+    }
+}
+
+impl Schematic {
+    fn part_numbers(&self) -> impl Iterator<Item = u32> + '_ {
+        self.numbers
+            .iter()
+            .filter(|number| {
+                self.symbols
+                    .iter()
+                    .any(|symbol| number.is_adjacent_to(symbol))
+            })
+            .map(|number| number.value)
+    }
+
+    fn gear_ratios(&self) -> impl Iterator<Item = u32> + '_ {
+        self.symbols
+            .iter()
+            .filter(|symbol| symbol.symbol == '*')
+            .filter_map(|symbol| {
+                let adjacent: Vec<u32> = self
+                    .numbers
+                    .iter()
+                    .filter(|number| number.is_adjacent_to(symbol))
+                    .map(|number| number.value)
+                    .collect();
+
+                match adjacent[..] {
+                    [a, b] => Some(a * b),
+                    _ => None,
+                }
+            })
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Parsed = Schematic;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        input.parse()
+    }
+
+    fn part1(schematic: &Self::Parsed) -> Result<Answer> {
+        Ok(schematic
+            .part_numbers()
+            .inspect(|number| trace!(number, "part number"))
+            .sum::<u32>()
+            .into())
+    }
+
+    fn part2(schematic: &Self::Parsed) -> Result<Answer> {
+        Ok(schematic
+            .gear_ratios()
+            .inspect(|ratio| trace!(ratio, "gear"))
+            .sum::<u32>()
+            .into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TEST: &str = include_str!("../../examples/day03/example.txt");
+
+    #[test]
+    fn test_parse() {
+        let schematic: Schematic = "...a..123...".parse().unwrap();
+        assert_eq!(
+            schematic.numbers,
+            [PartNumber {
+                value: 123,
+                row: 0,
+                start: 6,
+                end: 8
+            }]
+        );
+        assert_eq!(
+            schematic.symbols,
+            [Symbol {
+                symbol: 'a',
+                row: 0,
+                column: 3
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = "............*\n.99999999999*\n"
+            .parse::<Schematic>()
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "2:2: number too large: '99999999999'");
+
+        let err = "..é.\n.99999999999\n".parse::<Schematic>().err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "2:1: expected a row of 4 cells, found '.99999999999'"
+        );
+
+        let err = "é..*........\n.é9999999999\n"
+            .parse::<Schematic>()
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "2:3: number too large: '9999999999'");
+    }
+
+    #[test]
+    fn test_part_numbers() {
+        let schematic: Schematic = TEST.parse().unwrap();
+        assert!(!schematic.part_numbers().any(|n| n == 114 || n == 58));
+        assert_eq!(Day3::part1(&schematic).unwrap(), 4361);
+    }
+
+    #[test]
+    fn test_gear_ratios() {
+        let schematic: Schematic = TEST.parse().unwrap();
+        assert_eq!(schematic.gear_ratios().collect::<Vec<_>>(), [16345, 451490]);
+        assert_eq!(Day3::part2(&schematic).unwrap(), 467835);
+    }
+}
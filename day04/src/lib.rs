@@ -0,0 +1,263 @@
+use aoc_common::{Answer, Generated, ParseError, Parser, Result, Rng, Solution};
+#[cfg(test)]
+use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use tracing::trace;
+
+#[derive(Debug)]
+pub struct Card {
+    number: u32,
+    winners: HashSet<u32>,
+    results: HashSet<u32>,
+}
+
+impl Card {
+    // 'Card <number>: <winners> | <results>'
+    fn parse(p: &mut Parser) -> Result<Card, ParseError> {
+        p.literal("Card")?;
+        p.spaces();
+        let number = p.number()?;
+        p.label("")?;
+        let winners = p.numbers()?.into_iter().collect();
+        p.literal("|")?;
+        let results = p.numbers()?.into_iter().collect();
+
+        Ok(Card {
+            number,
+            winners,
+            results,
+        })
+    }
+
+    fn wins(&self) -> u32 {
+        self.winners.intersection(&self.results).count() as u32
+    }
+
+    fn score(&self) -> u32 {
+        match self.wins() {
+            0 => 0,
+            wins => 1 << (wins - 1),
+        }
+    }
+}
+
+// queues up every copy won as a card of its own and scratches them one by
+// one, as the puzzle tells it
+#[cfg(test)]
+fn count_by_simulation(cards: &HashMap<u32, Card>) -> u64 {
+    let mut count = 0;
+    let mut to_process: VecDeque<u32> = cards.keys().cloned().collect();
+    while let Some(card_number) = to_process.pop_front() {
+        count += 1;
+
+        let card = cards.get(&card_number).unwrap();
+        for i in card.number + 1..card.number + card.wins() + 1 {
+            to_process.push_back(i);
+        }
+    }
+    count
+}
+
+// `cards` scratchcards winning at most one copy each, so the number of copies
+// grows quadratically rather than exponentially
+fn generate(rng: &mut Rng, cards: usize) -> Generated {
+    let mut input = String::new();
+    let (mut score, mut copies, mut total) = (0_u64, 0_u64, 0_u64);
+    for card in 1..=cards {
+        let wins = if card == cards {
+            0
+        } else {
+            rng.below(2) as usize
+        };
+        let mut numbers: Vec<u32> = (1..100).collect();
+        rng.shuffle(&mut numbers);
+        let (winners, rest) = numbers.split_at(10);
+        let results = winners[..wins].iter().chain(&rest[..25 - wins]);
+
+        write!(input, "Card {card:>3}:").unwrap();
+        for n in winners {
+            write!(input, " {n:>2}").unwrap();
+        }
+        input.push_str(" |");
+        for n in results {
+            write!(input, " {n:>2}").unwrap();
+        }
+        input.push('\n');
+
+        // one original, plus a copy for every copy of the card before if it won
+        copies += 1;
+        total += copies;
+        score += wins as u64;
+        if wins == 0 {
+            copies = 0;
+        }
+    }
+
+    Generated {
+        input,
+        answers: vec![(1, score.into()), (2, total.into())],
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    type Parsed = HashMap<u32, Card>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Ok(Parser::new(input)
+            .each_line(Card::parse)?
+            .into_iter()
+            .map(|card| (card.number, card))
+            .collect())
+    }
+
+    fn part1(cards: &Self::Parsed) -> Result<Answer> {
+        Ok(cards
+            .values()
+            .map(|card| {
+                let score = card.score();
+                trace!(card = card.number, score);
+                score
+            })
+            .sum::<u32>()
+            .into())
+    }
+
+    // each card's copies win one more copy each of the next few cards, so
+    // going through them in order, every card's count is final by the time
+    // it's reached
+    fn part2(cards: &Self::Parsed) -> Result<Answer> {
+        let mut numbers: Vec<u32> = cards.keys().copied().collect();
+        numbers.sort_unstable();
+        let mut copies: HashMap<u32, u64> = numbers.iter().map(|&number| (number, 1)).collect();
+
+        let mut count = 0;
+        for number in numbers {
+            let card_copies = copies[&number];
+            count += card_copies;
+
+            let wins = cards[&number].wins();
+            trace!(card = number, wins, copies = card_copies, "visiting card");
+            for won in number + 1..number + wins + 1 {
+                *copies.get_mut(&won).ok_or_else(|| {
+                    format!("card {number} wins a copy of card {won}, which doesn't exist")
+                })? += card_copies;
+            }
+        }
+
+        Ok(count.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::differential;
+
+    #[test]
+    fn test_parsing() {
+        assert_eq!(
+            Card::parse(&mut Parser::new(
+                "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"
+            ))
+            .unwrap()
+            .score(),
+            8
+        );
+        assert_eq!(
+            Card::parse(&mut Parser::new(
+                "Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19"
+            ))
+            .unwrap()
+            .score(),
+            2
+        );
+        assert_eq!(
+            Card::parse(&mut Parser::new(
+                "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1"
+            ))
+            .unwrap()
+            .score(),
+            2
+        );
+        assert_eq!(
+            Card::parse(&mut Parser::new(
+                "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83"
+            ))
+            .unwrap()
+            .score(),
+            1
+        );
+        assert_eq!(
+            Card::parse(&mut Parser::new(
+                "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36"
+            ))
+            .unwrap()
+            .score(),
+            0
+        );
+        assert_eq!(
+            Card::parse(&mut Parser::new(
+                "Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
+            ))
+            .unwrap()
+            .score(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_copies_random() {
+        // a card can win as many cards as there are after it
+        let random_cards = |rng: &mut Rng| -> HashMap<u32, Card> {
+            let total = rng.range(1..12) as u32;
+            (1..=total)
+                .map(|number| {
+                    let wins = rng.below(u64::from(total - number).min(5) + 1) as u32;
+                    let card = Card {
+                        number,
+                        winners: (1..=5).collect(),
+                        results: (1..=wins).chain(10..15).collect(),
+                    };
+                    (number, card)
+                })
+                .collect()
+        };
+        differential(
+            200,
+            random_cards,
+            |cards| Day4::part2(cards).unwrap(),
+            |cards| count_by_simulation(cards).into(),
+        );
+    }
+
+    #[test]
+    fn test_missing_card() {
+        let cards = Day4::parse("Card 1: 41 48 | 41 48\nCard 2: 13 | 61\n").unwrap();
+        let err = Day4::part2(&cards).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "card 1 wins a copy of card 3, which doesn't exist"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day4::parse("Card 1: 41 48 | 83 86\nCard 2 13 32 | 61 30\n").unwrap_err();
+        assert_eq!(err.to_string(), "2:8: expected ':', found '13 32 | 61 30'");
+
+        let err = Day4::parse("Card 1: 41 99999999999 | 83 86\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "1:12: invalid number '99999999999': number too large to fit in target type"
+        );
+    }
+}
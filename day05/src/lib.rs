@@ -0,0 +1,397 @@
+use aoc_common::{
+    Answer, Generated, Interval, IntervalSet, OffsetMap, ParseError, Parser, Result, Rng, Solution,
+};
+use itertools::Itertools;
+use std::fmt::Write;
+use std::str::FromStr;
+use std::vec::Vec;
+use tracing::trace;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Range {
+    source_start: u32,
+    length: u32,
+    dest_start: u32,
+}
+
+impl Range {
+    // '<destination start> <source start> <length>'
+    fn parse(p: &mut Parser) -> Result<Range, ParseError> {
+        let line = p.rest();
+        if let [dest_start, source_start, length] = p.numbers()?[..] {
+            Ok(Range {
+                source_start,
+                length,
+                dest_start,
+            })
+        } else {
+            Err(p.expected(
+                line.trim_end(),
+                "'<destination start> <source start> <length>'",
+            ))
+        }
+    }
+
+    fn source(&self) -> Interval {
+        let start = i64::from(self.source_start);
+        Interval::new(start, start + i64::from(self.length))
+    }
+
+    fn offset(&self) -> i64 {
+        i64::from(self.dest_start) - i64::from(self.source_start)
+    }
+}
+
+struct Map {
+    offsets: OffsetMap,
+    #[cfg(test)]
+    ranges: Vec<Range>,
+}
+
+impl Map {
+    // a '<name> map:' header followed by one range per line
+    fn parse(p: &mut Parser) -> Result<Map, ParseError> {
+        p.until(" map:")?;
+        p.end_of_line()?;
+
+        // where ranges overlap, the first in sorted order wins
+        let mut ranges = p.each_line(Range::parse)?;
+        ranges.sort();
+        let mut offsets = OffsetMap::new();
+        for range in &ranges {
+            offsets.insert_uncovered(range.source(), range.offset());
+        }
+
+        Ok(Map {
+            offsets,
+            #[cfg(test)]
+            ranges,
+        })
+    }
+
+    // where a single value ends up, found by looking through the ranges one
+    // at a time
+    #[cfg(test)]
+    fn translate(&self, input: i64) -> i64 {
+        for range in &self.ranges {
+            let source_start = i64::from(range.source_start);
+            if source_start > input {
+                break;
+            }
+
+            let offset = input - source_start;
+            if offset < i64::from(range.length) {
+                return i64::from(range.dest_start) + offset;
+            }
+        }
+
+        input
+    }
+}
+
+pub struct Atlas {
+    seeds: Vec<u32>,
+    maps: Vec<Map>,
+}
+
+impl FromStr for Atlas {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut p = Parser::new(s);
+        let mut blocks = p.blocks();
+
+        let mut seeds_block = blocks.next().ok_or_else(|| p.expected_here("'seeds:'"))?;
+        seeds_block.label("seeds")?;
+        let seeds = seeds_block.numbers()?;
+        seeds_block.end()?;
+
+        let maps = blocks
+            .map(|mut block| Map::parse(&mut block))
+            .collect::<Result<Vec<Map>, ParseError>>()?;
+        if maps.is_empty() {
+            return Err(p.expected_here("a blank line after the seeds"));
+        }
+
+        Ok(Atlas { seeds, maps })
+    }
+}
+
+impl Atlas {
+    #[cfg(test)]
+    fn location_for_seed(&self, seed: i64) -> i64 {
+        self.maps
+            .iter()
+            .fold(seed, |value, map| map.translate(value))
+    }
+
+    fn seeds(&self) -> IntervalSet {
+        self.seeds
+            .iter()
+            .map(|&seed| Interval::new(seed.into(), i64::from(seed) + 1))
+            .collect()
+    }
+
+    // part 2 reinterprets the seeds line as pairs of (start, length)
+    fn seed_ranges(&self) -> IntervalSet {
+        self.seeds
+            .iter()
+            .tuples()
+            .map(|(&start, &length)| {
+                Interval::new(start.into(), i64::from(start) + i64::from(length))
+            })
+            .collect()
+    }
+
+    // pushes whole ranges of seeds through each map in turn, rather than
+    // following every seed on its own
+    fn locations(&self, seeds: &IntervalSet) -> Option<IntervalSet> {
+        self.maps
+            .iter()
+            .enumerate()
+            .try_fold(seeds.clone(), |set, (i, map)| {
+                let mapped = map.offsets.map_set(&set)?;
+                trace!(map = i + 1, intervals = mapped.iter().count(), "mapped");
+                Some(mapped)
+            })
+    }
+
+    fn lowest_location(&self, seeds: &IntervalSet) -> Result<i64> {
+        let locations = self
+            .locations(seeds)
+            .ok_or("a seed's location is too large")?;
+        Ok(locations.min().ok_or("no seeds in almanac")?)
+    }
+
+    // follows the seeds through the maps one at a time
+    #[cfg(test)]
+    fn lowest_location_by_seed(&self, seeds: &IntervalSet) -> Option<i64> {
+        seeds
+            .iter()
+            .flat_map(|range| range.start..range.end)
+            .map(|seed| self.location_for_seed(seed))
+            .min()
+    }
+}
+
+// the most seeds the generator will follow one by one to work out part 2
+const CHEAP_SEEDS: usize = 100_000;
+
+// an almanac whose seed ranges cover `seeds` seeds in all, answering part 2
+// too when that's few enough to follow each seed
+fn generate(rng: &mut Rng, seeds: usize) -> Generated {
+    const LIMIT: u64 = 1 << 32;
+    let pairs = seeds.clamp(1, 10);
+    let ranges: Vec<(u64, u64)> = (0..pairs)
+        .map(|i| {
+            let length = ((seeds / pairs + usize::from(i < seeds % pairs)) as u64).min(LIMIT / 2);
+            (rng.below(LIMIT - length), length)
+        })
+        .collect();
+
+    // each map's sources come from consecutive pairs of distinct points, so
+    // they can't overlap
+    let maps: Vec<Vec<(u64, u64, u64)>> = (0..7)
+        .map(|_| {
+            let mut points: Vec<u64> = (0..2 * rng.range(3..12))
+                .map(|_| rng.below(LIMIT))
+                .collect();
+            points.sort_unstable();
+            points.dedup();
+            let mut map: Vec<(u64, u64, u64)> = points
+                .chunks_exact(2)
+                .map(|pair| {
+                    let length = pair[1] - pair[0];
+                    (rng.below(LIMIT - length), pair[0], length)
+                })
+                .collect();
+            rng.shuffle(&mut map);
+            map
+        })
+        .collect();
+
+    let location = |seed: u64| {
+        maps.iter().fold(seed, |value, map| {
+            map.iter()
+                .find(|&&(_, source, length)| (source..source + length).contains(&value))
+                .map_or(value, |&(dest, source, _)| value - source + dest)
+        })
+    };
+    let numbers = ranges.iter().flat_map(|&(start, length)| [start, length]);
+    let mut answers = vec![(1, numbers.clone().map(location).min().unwrap().into())];
+    if seeds <= CHEAP_SEEDS {
+        let lowest = ranges
+            .iter()
+            .flat_map(|&(start, length)| start..start + length)
+            .map(location)
+            .min();
+        answers.extend(lowest.map(|lowest| (2, lowest.into())));
+    }
+
+    let mut input = String::from("seeds:");
+    for n in numbers {
+        write!(input, " {n}").unwrap();
+    }
+    input.push('\n');
+    let names = [
+        "seed",
+        "soil",
+        "fertilizer",
+        "water",
+        "light",
+        "temperature",
+        "humidity",
+        "location",
+    ];
+    for (pair, map) in names.windows(2).zip(&maps) {
+        write!(input, "\n{}-to-{} map:\n", pair[0], pair[1]).unwrap();
+        for (dest, source, length) in map {
+            writeln!(input, "{dest} {source} {length}").unwrap();
+        }
+    }
+
+    Generated { input, answers }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Parsed = Atlas;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        input.parse()
+    }
+
+    fn part1(atlas: &Self::Parsed) -> Result<Answer> {
+        Ok(atlas.lowest_location(&atlas.seeds())?.into())
+    }
+
+    fn part2(atlas: &Self::Parsed) -> Result<Answer> {
+        Ok(atlas.lowest_location(&atlas.seed_ranges())?.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::differential;
+
+    const TEST: &str = include_str!("../../examples/day05/example.txt");
+
+    #[test]
+    fn test_parse() {
+        let atlas: Atlas = TEST.parse().unwrap();
+
+        assert_eq!(atlas.seeds().to_string(), "{[13, 15), [55, 56), [79, 80)}");
+        assert_eq!(atlas.seed_ranges().to_string(), "{[55, 68), [79, 93)}");
+
+        let location_for_seed = |seed| atlas.lowest_location(&Interval::new(seed, seed + 1).into());
+        assert_eq!(location_for_seed(79).unwrap(), 82);
+        assert_eq!(location_for_seed(14).unwrap(), 43);
+        assert_eq!(location_for_seed(55).unwrap(), 86);
+        assert_eq!(location_for_seed(13).unwrap(), 35);
+    }
+
+    #[test]
+    fn test_locations_brute_force() {
+        // following each seed on its own lands on the same locations
+        let atlas: Atlas = TEST.parse().unwrap();
+        let seeds = atlas.seed_ranges();
+        let expected: IntervalSet = seeds
+            .iter()
+            .flat_map(|range| range.start..range.end)
+            .map(|seed| {
+                let location = atlas.location_for_seed(seed);
+                Interval::new(location, location + 1)
+            })
+            .collect();
+        assert_eq!(atlas.locations(&seeds), Some(expected));
+    }
+
+    // a small almanac whose ranges keep running into each other
+    fn random_almanac(rng: &mut Rng) -> String {
+        let mut input = String::from("seeds:");
+        for _ in 0..rng.range(1..4) {
+            write!(input, " {} {}", rng.below(64), rng.below(16)).unwrap();
+        }
+        input.push('\n');
+
+        for map in 0..rng.range(1..5) {
+            write!(input, "\n{map}-to-{} map:\n", map + 1).unwrap();
+            for _ in 0..rng.range(1..5) {
+                let (dest, source, length) = (rng.below(96), rng.below(96), rng.below(32));
+                writeln!(input, "{dest} {source} {length}").unwrap();
+            }
+        }
+        input
+    }
+
+    #[test]
+    fn test_lowest_location_random() {
+        let lowest_locations = |lowest: fn(&Atlas, &IntervalSet) -> Option<i64>| {
+            move |input: &String| {
+                let atlas: Atlas = input.parse().unwrap();
+                [atlas.seeds(), atlas.seed_ranges()].map(|seeds| lowest(&atlas, &seeds))
+            }
+        };
+        differential(
+            500,
+            random_almanac,
+            lowest_locations(|atlas, seeds| atlas.lowest_location(seeds).ok()),
+            lowest_locations(Atlas::lowest_location_by_seed),
+        );
+    }
+
+    #[test]
+    fn test_overlapping_ranges() {
+        // the range that starts first takes the values both cover
+        let atlas: Atlas = "seeds: 5 15\n\nx-to-y map:\n100 10 10\n0 0 20\n"
+            .parse()
+            .unwrap();
+        assert_eq!(atlas.location_for_seed(5), 5);
+        assert_eq!(atlas.location_for_seed(15), 15);
+        assert_eq!(atlas.lowest_location(&atlas.seeds()).unwrap(), 5);
+
+        let atlas: Atlas = "seeds: 5 15\n\nx-to-y map:\n100 5 10\n0 0 10\n"
+            .parse()
+            .unwrap();
+        assert_eq!(atlas.location_for_seed(5), 5);
+        assert_eq!(atlas.location_for_seed(12), 107);
+        assert_eq!(atlas.lowest_location(&atlas.seed_ranges()).unwrap(), 5);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = TEST
+            .replace("52 50 48", "52 50")
+            .parse::<Atlas>()
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "5:1: expected '<destination start> <source start> <length>', found '52 50'"
+        );
+
+        let err = TEST
+            .replace("39 0 15", "39 0 1x")
+            .parse::<Atlas>()
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "10:6: invalid number '1x': invalid digit found in string"
+        );
+
+        let err = "seeds: 79 14\n".parse::<Atlas>().err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "2:1: expected a blank line after the seeds, found end of input"
+        );
+    }
+}
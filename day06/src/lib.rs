@@ -0,0 +1,281 @@
+use aoc_common::{math, Answer, Generated, ParseError, Parser, Result, Rng, Solution};
+use std::str::FromStr;
+use std::vec::Vec;
+use tracing::trace;
+
+#[derive(Debug, PartialEq, Eq)]
+struct Race {
+    time: u64,
+    distance: u64,
+}
+
+impl Race {
+    fn distance_traveled(&self, hold_time: u64) -> u128 {
+        hold_time as u128 * (self.time - hold_time) as u128
+    }
+
+    // Holding for h wins when h(T - h) > D, i.e. between the roots of
+    // h² - Th + D, which sit symmetrically either side of T/2. So find the
+    // first winning hold time and count to its mirror image.
+    fn ways_to_win(&self) -> u64 {
+        let (time, distance) = (self.time as u128, self.distance as u128);
+        let Some(discriminant) = (time * time).checked_sub(4 * distance) else {
+            return 0;
+        };
+
+        // the integer root is within one of the real one, so this starts
+        // at or just before the first win
+        let root = math::isqrt_u128(discriminant) as u64;
+        let mut first_win = (self.time - root) / 2;
+        while first_win <= self.time / 2 && self.distance_traveled(first_win) <= distance {
+            first_win += 1;
+        }
+
+        if first_win > self.time / 2 {
+            0
+        } else {
+            self.time - 2 * first_win + 1
+        }
+    }
+
+    // races with every hold time
+    #[cfg(test)]
+    fn ways_to_win_by_trying(&self) -> u64 {
+        (0..=self.time)
+            .filter(|&hold_time| self.distance_traveled(hold_time) > self.distance as u128)
+            .count() as u64
+    }
+}
+
+pub struct Sheet {
+    races: Vec<Race>,
+}
+
+impl FromStr for Sheet {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut p = Parser::new(s);
+        p.label("Time")?;
+        let times: Vec<u64> = p.numbers()?;
+        p.end_of_line()?;
+
+        let distance_line = p.rest().lines().next().unwrap_or_default();
+        p.label("Distance")?;
+        let distances: Vec<u64> = p.numbers()?;
+        if times.len() != distances.len() {
+            return Err(p.expected(distance_line, format!("{} distances", times.len())));
+        }
+        p.end()?;
+
+        let races = times
+            .iter()
+            .zip(distances)
+            .map(|(&time, distance)| Race { time, distance })
+            .collect();
+        Ok(Self { races })
+    }
+}
+
+impl Sheet {
+    // part 2 reveals the columns were really one number with bad kerning
+    fn kerned_race(&self) -> Result<Race> {
+        let concat = |numbers: Vec<u64>| -> Result<u64> {
+            Ok(numbers
+                .iter()
+                .map(u64::to_string)
+                .collect::<String>()
+                .parse()?)
+        };
+
+        Ok(Race {
+            time: concat(self.races.iter().map(|race| race.time).collect())?,
+            distance: concat(self.races.iter().map(|race| race.distance).collect())?,
+        })
+    }
+}
+
+// the longest race the generator will try every hold time of
+const CHEAP_TIME: u64 = 10_000_000;
+
+// a single race lasting `time` milliseconds with a record just short of the
+// best possible, which is the same race with or without the kerning
+fn generate(rng: &mut Rng, time: usize) -> Generated {
+    let time = time as u64;
+    // the best is a quarter of time², which can be far too large for the
+    // sheet, but the record only has to fall short of it
+    let best = u128::from(time) * u128::from(time) / 4;
+    let short_by = 1 + rng.below(time.max(1));
+    let distance = best.saturating_sub(short_by.into()).min(u64::MAX.into()) as u64;
+    let mut answers = Vec::new();
+    if time <= CHEAP_TIME {
+        let ways = (0..=time)
+            .filter(|&hold_time| hold_time * (time - hold_time) > distance)
+            .count() as u64;
+        answers = vec![(1, ways.into()), (2, ways.into())];
+    }
+
+    Generated {
+        input: format!("Time: {time}\nDistance: {distance}\n"),
+        answers,
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Parsed = Sheet;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        input.parse()
+    }
+
+    fn part1(sheet: &Self::Parsed) -> Result<Answer> {
+        Ok(sheet
+            .races
+            .iter()
+            .map(|race| {
+                let ways = race.ways_to_win();
+                trace!(race.time, race.distance, ways);
+                ways
+            })
+            .product::<u64>()
+            .into())
+    }
+
+    fn part2(sheet: &Self::Parsed) -> Result<Answer> {
+        let race = sheet.kerned_race()?;
+        trace!(race.time, race.distance, "kerned race");
+        Ok(race.ways_to_win().into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::differential;
+
+    const TEST: &str = include_str!("../../examples/day06/example.txt");
+
+    #[test]
+    fn test_parsing() {
+        let sheet: Sheet = TEST.parse().unwrap();
+        assert_eq!(
+            sheet.races,
+            [
+                Race {
+                    time: 7,
+                    distance: 9
+                },
+                Race {
+                    time: 15,
+                    distance: 40
+                },
+                Race {
+                    time: 30,
+                    distance: 200
+                },
+            ]
+        );
+
+        let race = sheet.kerned_race().unwrap();
+        assert_eq!(race.time, 71530);
+        assert_eq!(race.distance, 940200);
+    }
+
+    #[test]
+    fn test_ways_to_win() {
+        let race = Race {
+            time: 7,
+            distance: 9,
+        };
+        assert_eq!(race.ways_to_win(), 4);
+
+        let race = Race {
+            time: 15,
+            distance: 40,
+        };
+        assert_eq!(race.ways_to_win(), 8);
+
+        let race = Race {
+            time: 30,
+            distance: 200,
+        };
+        assert_eq!(race.ways_to_win(), 9);
+    }
+
+    #[test]
+    fn test_ways_to_win_brute_force() {
+        for time in 0..60 {
+            for distance in 0..1000 {
+                let race = Race { time, distance };
+                assert_eq!(race.ways_to_win(), race.ways_to_win_by_trying(), "{race:?}");
+            }
+        }
+
+        let race = Race {
+            time: u64::MAX,
+            distance: 0,
+        };
+        assert_eq!(race.ways_to_win(), u64::MAX - 1);
+    }
+
+    #[test]
+    fn test_ways_to_win_random() {
+        // records either side of the best distance, where the roots are
+        // closest together, as well as ones that are easy to beat
+        let random_race = |rng: &mut Rng| {
+            let time = rng.below(100_000);
+            let best = time * time / 4;
+            let distance = match rng.below(2) {
+                0 => rng.range(best.saturating_sub(time)..best + 2),
+                _ => rng.below(best + 1),
+            };
+            Race { time, distance }
+        };
+        differential(
+            200,
+            random_race,
+            Race::ways_to_win,
+            Race::ways_to_win_by_trying,
+        );
+    }
+
+    #[test]
+    fn test_generate_long_race() {
+        // past u32::MAX the best distance no longer fits in a u64
+        for time in [1 << 32, u64::MAX] {
+            let generated = generate(&mut Rng::new(6), time as usize);
+            assert!(generated.answers.is_empty());
+            let race = Day6::parse(&generated.input)
+                .unwrap()
+                .kerned_race()
+                .unwrap();
+            assert_eq!(race.time, time);
+            assert!(race.ways_to_win() > 0, "{race:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day6::parse("Time:      7  15   30\nDistance:  9  40\n")
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "2:1: expected 3 distances, found 'Distance:  9  40'"
+        );
+
+        let err = Day6::parse("Time:      7  15   30\n").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "2:1: expected 'Distance:', found end of input"
+        );
+    }
+}
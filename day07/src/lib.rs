@@ -0,0 +1,498 @@
+use aoc_common::{Answer, Generated, ParseError, Parser, Result, Rng, Solution};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::str::FromStr;
+use tracing::trace;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone)]
+enum Card {
+    Joker,
+    One,
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+impl FromStr for Card {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "A" => Ok(Self::Ace),
+            "K" => Ok(Self::King),
+            "Q" => Ok(Self::Queen),
+            "J" => Ok(Self::Jack),
+            "T" => Ok(Self::Ten),
+            "9" => Ok(Self::Nine),
+            "8" => Ok(Self::Eight),
+            "7" => Ok(Self::Seven),
+            "6" => Ok(Self::Six),
+            "5" => Ok(Self::Five),
+            "4" => Ok(Self::Four),
+            "3" => Ok(Self::Three),
+            "2" => Ok(Self::Two),
+            "1" => Ok(Self::One),
+            _ => Err(ParseError::expected(s, s, "a card")),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfKind,
+    FullHouse,
+    FourOfKind,
+    FiveOfKind,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+struct Hand {
+    cards: [Card; 5],
+}
+
+impl FromStr for Hand {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cards: Vec<Card> = s
+            .char_indices()
+            .map(|(i, c)| {
+                let card = &s[i..i + c.len_utf8()];
+                card.parse::<Card>().map_err(|err| err.within(s, card))
+            })
+            .collect::<Result<Vec<Card>, ParseError>>()?;
+
+        let count = cards.len();
+        let cards = cards.try_into().map_err(|_| {
+            if count < 5 {
+                ParseError::unexpected_end(s, "5 cards")
+            } else {
+                ParseError::expected(s, s, "5 cards")
+            }
+        })?;
+        Ok(Hand { cards })
+    }
+}
+
+impl Hand {
+    // part 2 reveals that J cards are really jokers
+    fn with_jokers(&self) -> Hand {
+        Hand {
+            cards: self.cards.map(|card| match card {
+                Card::Jack => Card::Joker,
+                card => card,
+            }),
+        }
+    }
+
+    fn hand_type(&self) -> HandType {
+        let mut counts: Vec<usize> = self
+            .cards
+            .iter()
+            .filter(|&card| *card != Card::Joker)
+            .fold(HashMap::with_capacity(5), |mut counter, &card| {
+                counter
+                    .entry(card)
+                    .and_modify(|counter| *counter += 1)
+                    .or_insert(1);
+                counter
+            })
+            .into_values()
+            .collect();
+        counts.sort();
+
+        let joker_count: usize = 5 - counts.iter().sum::<usize>();
+        match counts[..] {
+            // QQ 2
+
+            // JJJJJ => []
+            [] => HandType::FiveOfKind,
+            // AAAAA => [A], AAAAJ => [A], AAAJJ => [A], AAJJJ => [A], AJJJJ => [A]
+            [_] => HandType::FiveOfKind,
+
+            //
+            [_, x] => {
+                if x + joker_count == 4 {
+                    HandType::FourOfKind
+                } else {
+                    HandType::FullHouse
+                }
+            }
+
+            // [1, 2, 3] 3ok; [11, 2, 3] 3ok; [111, 2, 3] 3ok; [11, 22, 3] 2pair
+            [_, y, x] => {
+                if x != 2 || y != 2 {
+                    HandType::ThreeOfKind
+                } else {
+                    HandType::TwoPair
+                }
+            }
+
+            [_, _, _, _] => HandType::OnePair,
+
+            // 12345
+            _ => HandType::HighCard,
+        }
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match self.hand_type().cmp(&other.hand_type()) {
+            // if equal, go in order of cards comparing using card rank
+            Ordering::Equal => self.cards.cmp(&other.cards),
+            // otherwise hand type takes precedence
+            ordering => ordering,
+        }
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone)]
+struct Play {
+    hand: Hand,
+    bid: u32,
+}
+
+impl Play {
+    // '<hand> <bid>'
+    fn parse(p: &mut Parser) -> Result<Play, ParseError> {
+        let line = p.rest();
+        let hand = p.parse_word()?;
+        if !p.eat(" ") {
+            return Err(p.expected(line, "'<hand> <bid>'"));
+        }
+        p.spaces();
+        let bid = p.number()?;
+
+        Ok(Play { hand, bid })
+    }
+}
+
+pub struct Game {
+    plays: Vec<Play>,
+}
+
+impl FromStr for Game {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Game {
+            plays: Parser::new(s).each_line(Play::parse)?,
+        })
+    }
+}
+
+impl Game {
+    fn with_jokers(&self) -> Game {
+        Game {
+            plays: self
+                .plays
+                .iter()
+                .map(|play| Play {
+                    hand: play.hand.with_jokers(),
+                    bid: play.bid,
+                })
+                .collect(),
+        }
+    }
+
+    fn winnings(&self) -> impl Iterator<Item = u64> + '_ {
+        let mut ranked: Vec<&Play> = self.plays.iter().collect();
+        ranked.sort();
+
+        ranked.into_iter().enumerate().map(|(i, play)| {
+            trace!(rank = i + 1, hand = ?play.hand.cards, play.bid);
+            u64::from(play.bid) * (i as u64 + 1)
+        })
+    }
+}
+
+// there are only so many different hands, and duplicates would tie
+const MAX_HANDS: usize = 100_000;
+
+// how strong a hand is, worked out from scratch: the sizes of its groups of
+// like cards, biggest first, then its cards in the order given by `labels`
+fn strength(hand: &[u8], labels: &str, jokers: bool) -> (Vec<usize>, Vec<usize>) {
+    let mut counts: HashMap<u8, usize> = HashMap::new();
+    for &card in hand {
+        *counts.entry(card).or_default() += 1;
+    }
+    let wild = if jokers {
+        counts.remove(&b'J').unwrap_or(0)
+    } else {
+        0
+    };
+    let mut groups: Vec<usize> = counts.into_values().collect();
+    groups.sort_unstable_by(|a, b| b.cmp(a));
+    match groups.first_mut() {
+        Some(biggest) => *biggest += wild,
+        None => groups.push(wild),
+    }
+
+    let values = hand
+        .iter()
+        .map(|&card| labels.find(card as char).unwrap())
+        .collect();
+    (groups, values)
+}
+
+// `hands` different hands spread evenly over the types, with their bids
+fn generate(rng: &mut Rng, hands: usize) -> Generated {
+    const SHAPES: [&[usize]; 7] = [
+        &[5],
+        &[4, 1],
+        &[3, 2],
+        &[3, 1, 1],
+        &[2, 2, 1],
+        &[2, 1, 1, 1],
+        &[1, 1, 1, 1, 1],
+    ];
+    let mut seen = HashSet::new();
+    let mut plays = Vec::new();
+    while plays.len() < hands.min(MAX_HANDS) {
+        let mut labels = *b"23456789TJQKA";
+        rng.shuffle(&mut labels);
+        let shape = rng.choose(&SHAPES);
+        let mut hand: Vec<u8> = shape
+            .iter()
+            .zip(labels)
+            .flat_map(|(&count, label)| [label].repeat(count))
+            .collect();
+        rng.shuffle(&mut hand);
+        if seen.insert(hand.clone()) {
+            plays.push((hand, rng.range(1..1001)));
+        }
+    }
+
+    let winnings = |labels: &str, jokers: bool| -> u64 {
+        let mut ranked: Vec<_> = plays
+            .iter()
+            .map(|(hand, bid)| (strength(hand, labels, jokers), bid))
+            .collect();
+        ranked.sort();
+        ranked
+            .iter()
+            .enumerate()
+            .map(|(i, (_, &bid))| (i as u64 + 1) * bid)
+            .sum()
+    };
+
+    let mut input = String::new();
+    for (hand, bid) in &plays {
+        writeln!(input, "{} {bid}", String::from_utf8_lossy(hand)).unwrap();
+    }
+    Generated {
+        input,
+        answers: vec![
+            (1, winnings("23456789TJQKA", false).into()),
+            (2, winnings("J23456789TQKA", true).into()),
+        ],
+    }
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Parsed = Game;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        input.parse()
+    }
+
+    fn part1(game: &Self::Parsed) -> Result<Answer> {
+        Ok(game.winnings().sum::<u64>().into())
+    }
+
+    fn part2(game: &Self::Parsed) -> Result<Answer> {
+        Ok(game.with_jokers().winnings().sum::<u64>().into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_cards() {
+        assert_eq!(Card::from_str("A").unwrap(), Card::Ace);
+        assert_eq!(Card::from_str("K").unwrap(), Card::King);
+        assert_eq!(Card::from_str("Q").unwrap(), Card::Queen);
+        assert_eq!(Card::from_str("J").unwrap(), Card::Jack);
+        assert_eq!(Card::from_str("T").unwrap(), Card::Ten);
+        assert_eq!(Card::from_str("9").unwrap(), Card::Nine);
+        assert_eq!(Card::from_str("8").unwrap(), Card::Eight);
+        assert_eq!(Card::from_str("7").unwrap(), Card::Seven);
+        assert_eq!(Card::from_str("6").unwrap(), Card::Six);
+        assert_eq!(Card::from_str("5").unwrap(), Card::Five);
+        assert_eq!(Card::from_str("4").unwrap(), Card::Four);
+        assert_eq!(Card::from_str("3").unwrap(), Card::Three);
+        assert_eq!(Card::from_str("2").unwrap(), Card::Two);
+        assert_eq!(Card::from_str("1").unwrap(), Card::One);
+        assert!(Card::from_str("X").is_err());
+    }
+
+    #[test]
+    fn test_ordering_cards() {
+        assert!(Card::Ace > Card::King);
+        assert!(Card::Ten < Card::Queen);
+        assert!(Card::Jack > Card::Ten);
+        assert!(Card::Joker < Card::Two);
+        assert!(Card::Two == Card::Two);
+    }
+
+    #[test]
+    fn test_parse_hand() {
+        assert_eq!(
+            "AAAAA".parse::<Hand>().unwrap().cards,
+            [Card::Ace, Card::Ace, Card::Ace, Card::Ace, Card::Ace]
+        );
+
+        assert_eq!(
+            "T259K".parse::<Hand>().unwrap().cards,
+            [Card::Ten, Card::Two, Card::Five, Card::Nine, Card::King]
+        );
+
+        assert!("X".parse::<Hand>().is_err());
+        assert!("AA".parse::<Hand>().is_err());
+    }
+
+    #[test]
+    fn test_hand_type() {
+        assert_eq!(
+            "AAAAA".parse::<Hand>().unwrap().hand_type(),
+            HandType::FiveOfKind
+        );
+
+        assert_eq!(
+            "AA8AA".parse::<Hand>().unwrap().hand_type(),
+            HandType::FourOfKind
+        );
+
+        assert_eq!(
+            "23332".parse::<Hand>().unwrap().hand_type(),
+            HandType::FullHouse
+        );
+
+        assert_eq!(
+            "TTT98".parse::<Hand>().unwrap().hand_type(),
+            HandType::ThreeOfKind
+        );
+
+        assert_eq!(
+            "23432".parse::<Hand>().unwrap().hand_type(),
+            HandType::TwoPair
+        );
+
+        assert_eq!(
+            "A23A4".parse::<Hand>().unwrap().hand_type(),
+            HandType::OnePair
+        );
+
+        assert_eq!(
+            "23456".parse::<Hand>().unwrap().hand_type(),
+            HandType::HighCard
+        );
+
+        assert_eq!(
+            "QJJQ2".parse::<Hand>().unwrap().hand_type(),
+            HandType::TwoPair
+        );
+    }
+
+    #[test]
+    fn test_hand_type_with_jokers() {
+        assert_eq!(
+            "QJJQ2".parse::<Hand>().unwrap().with_jokers().hand_type(),
+            HandType::FourOfKind
+        );
+
+        assert_eq!(
+            "JJJJJ".parse::<Hand>().unwrap().with_jokers().hand_type(),
+            HandType::FiveOfKind
+        );
+
+        assert_eq!(
+            "T55J5".parse::<Hand>().unwrap().with_jokers().hand_type(),
+            HandType::FourOfKind
+        );
+
+        assert_eq!(
+            "2345J".parse::<Hand>().unwrap().with_jokers().hand_type(),
+            HandType::OnePair
+        );
+    }
+
+    #[test]
+    fn test_hand_ordering() {
+        assert!("11111".parse::<Hand>().unwrap() > "AAAAK".parse::<Hand>().unwrap());
+        assert!("33332".parse::<Hand>().unwrap() > "2AAAA".parse::<Hand>().unwrap());
+        assert!("77888".parse::<Hand>().unwrap() > "77788".parse::<Hand>().unwrap());
+        assert!("QQQQ2".parse::<Hand>().unwrap() > "JKKK2".parse::<Hand>().unwrap());
+
+        let jkkk2 = "JKKK2".parse::<Hand>().unwrap().with_jokers();
+        assert!("QQQQ2".parse::<Hand>().unwrap().with_jokers() > jkkk2);
+        assert!("22223".parse::<Hand>().unwrap().with_jokers() > jkkk2);
+    }
+
+    #[test]
+    fn test_parse_play() {
+        let play = Play::parse(&mut Parser::new("32T3K 765")).unwrap();
+        assert_eq!(
+            play.hand,
+            Hand {
+                cards: [Card::Three, Card::Two, Card::Ten, Card::Three, Card::King]
+            }
+        );
+        assert_eq!(play.bid, 765);
+    }
+
+    const TEST_GAME: &str = include_str!("../../examples/day07/example.txt");
+
+    #[test]
+    fn test_parse_errors() {
+        let err = TEST_GAME
+            .replace("KTJJT", "KTJXT")
+            .parse::<Game>()
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "4:4: expected a card, found 'X'");
+
+        let err = "32T3K 765\nT55J5\n".parse::<Game>().err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "2:1: expected '<hand> <bid>', found 'T55J5'"
+        );
+
+        let err = "32T 765\n".parse::<Game>().err().unwrap();
+        assert_eq!(err.to_string(), "1:4: expected 5 cards, found end of input");
+
+        let err = "32T3KK 765\n".parse::<Game>().err().unwrap();
+        assert_eq!(err.to_string(), "1:1: expected 5 cards, found '32T3KK'");
+    }
+}
@@ -0,0 +1,398 @@
+use aoc_common::graph::{self, Cycle, Interner};
+use aoc_common::{math, Answer, Generated, ParseError, Parser, Result, Rng, Solution};
+use std::str::FromStr;
+use tracing::trace;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Direction {
+    Left,
+    Right,
+}
+
+fn parse_route(p: &mut Parser) -> Result<Vec<Direction>, ParseError> {
+    let route = p.word()?;
+    route
+        .char_indices()
+        .map(|(i, c)| match c {
+            'L' => Ok(Direction::Left),
+            'R' => Ok(Direction::Right),
+            _ => Err(p.expected(&route[i..i + c.len_utf8()], "'L' or 'R'")),
+        })
+        .collect()
+}
+
+struct Node<'a> {
+    left: &'a str,
+    right: &'a str,
+}
+
+impl<'a> Node<'a> {
+    // '(<left>, <right>)'
+    fn parse(p: &mut Parser<'a>) -> Result<Node<'a>, ParseError> {
+        p.literal("(")?;
+        let left = p.word()?;
+        p.literal(", ")?;
+        let right = p.word()?;
+        p.literal(")")?;
+        Ok(Node { left, right })
+    }
+}
+
+pub struct Map {
+    names: Interner<String>,
+    // the (left, right) neighbours of each node, indexed by id
+    nodes: Vec<(usize, usize)>,
+}
+
+impl FromStr for Map {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Map::parse(&mut Parser::new(s))
+    }
+}
+
+impl Map {
+    // one '<node> = (<left>, <right>)' per line
+    fn parse(p: &mut Parser) -> Result<Map, ParseError> {
+        let definitions = p.each_line(|line| {
+            let key = line.word()?;
+            line.literal(" = ")?;
+            Ok((key, Node::parse(line)?))
+        })?;
+
+        // number every node first, so definitions can refer to later ones
+        let mut names = Interner::new();
+        for (key, _) in &definitions {
+            names.intern(key.to_string());
+        }
+
+        let id = |name: &str| {
+            names
+                .id(name)
+                .ok_or_else(|| p.invalid(name, "unknown node"))
+        };
+        let mut nodes = vec![(0, 0); names.len()];
+        for (key, node) in &definitions {
+            nodes[id(key)?] = (id(node.left)?, id(node.right)?);
+        }
+
+        Ok(Map { names, nodes })
+    }
+
+    fn is_end(&self, node: usize) -> bool {
+        self.names.resolve(node).ends_with('Z')
+    }
+
+    fn step(&self, route: &[Direction], (node, i): (usize, usize)) -> (usize, usize) {
+        let (left, right) = self.nodes[node];
+        let next = match route[i] {
+            Direction::Left => left,
+            Direction::Right => right,
+        };
+        (next, (i + 1) % route.len())
+    }
+
+    // the steps after which a walk from `start` is at an end node, up until
+    // the walk starts repeating itself
+    fn arrivals(&self, route: &[Direction], start: usize) -> Result<(Vec<usize>, Cycle)> {
+        if route.is_empty() {
+            return Err("route is empty".into());
+        }
+
+        let cycle = graph::brent((start, 0), |&state| self.step(route, state));
+        let mut state = (start, 0);
+        let mut arrivals = Vec::new();
+        for steps in 1..=cycle.start + cycle.length {
+            state = self.step(route, state);
+            if self.is_end(state.0) {
+                arrivals.push(steps);
+            }
+        }
+        Ok((arrivals, cycle))
+    }
+
+    fn route_length(&self, route: &[Direction], start: usize) -> Result<usize> {
+        let (arrivals, _) = self.arrivals(route, start)?;
+        self.first_arrival(&arrivals, start)
+    }
+
+    fn first_arrival(&self, arrivals: &[usize], start: usize) -> Result<usize> {
+        arrivals.first().copied().ok_or_else(|| {
+            let name = self.names.resolve(start);
+            format!("the route from '{name}' never reaches a node ending in 'Z'").into()
+        })
+    }
+
+    fn camel_route_length(&self, route: &[Direction]) -> Result<usize> {
+        let start = self.names.id("AAA").ok_or("unknown node 'AAA'")?;
+        self.route_length(route, start)
+    }
+
+    // how often a ghost starting at `start` is at an end node, which only
+    // makes sense if it gets there on exactly the multiples of some period
+    fn ghost_period(&self, route: &[Direction], start: usize) -> Result<u64> {
+        let (arrivals, cycle) = self.arrivals(route, start)?;
+        let period = self.first_arrival(&arrivals, start)?;
+
+        // past the end of `arrivals` the walk repeats every `cycle.length`
+        // steps, so if that's a multiple of the period too, this holds forever
+        let every_period = (period..=cycle.start + cycle.length).step_by(period);
+        if !arrivals.iter().copied().eq(every_period) || cycle.length % period != 0 {
+            let name = self.names.resolve(start);
+            return Err(
+                format!("the ghost from '{name}' doesn't reach the end at a steady pace").into(),
+            );
+        }
+        trace!(start = self.names.resolve(start), period, "ghost");
+        Ok(period as u64)
+    }
+
+    fn ghost_route_length(&self, route: &[Direction]) -> Result<u64> {
+        let periods = self
+            .names
+            .iter()
+            .filter(|(_, name)| name.ends_with('A'))
+            .map(|(start, _)| self.ghost_period(route, start))
+            .collect::<Result<Vec<u64>>>()?;
+        if periods.is_empty() {
+            return Err("no starting nodes found".into());
+        }
+
+        Ok(math::lcm_all(periods).ok_or("the ghosts' route is too long to count")?)
+    }
+}
+
+pub struct Documents {
+    route: Vec<Direction>,
+    map: Map,
+}
+
+impl FromStr for Documents {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut p = Parser::new(s);
+        let mut blocks = p.blocks();
+
+        let mut route_block = blocks.next().ok_or_else(|| p.expected_here("a route"))?;
+        let route = parse_route(&mut route_block)?;
+        route_block.end()?;
+
+        let mut map_block = blocks
+            .next()
+            .ok_or_else(|| p.expected_here("a blank line after the route"))?;
+        let map = Map::parse(&mut map_block)?;
+        if let Some(extra) = blocks.next() {
+            return Err(p.expected(extra.rest(), "end of input"));
+        }
+
+        Ok(Documents { route, map })
+    }
+}
+
+// a name for the `n`th node of its kind, ending in `last`: three characters
+// like the puzzle's while there are few enough nodes, and longer after that
+fn node_name(mut n: usize, last: char) -> String {
+    const DIGITS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    let mut name = vec![last as u8];
+    for _ in 0..2 {
+        name.push(DIGITS[n % DIGITS.len()]);
+        n /= DIGITS.len();
+    }
+    while n > 0 {
+        name.push(DIGITS[n % DIGITS.len()]);
+        n /= DIGITS.len();
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap()
+}
+
+// a network of roughly `nodes` nodes for six ghosts, one of them starting at
+// AAA and ending at ZZZ. Each ghost's path is two lanes of nodes, where L
+// takes the first lane and R the second, that meet again at its end and then
+// go round again, so it reaches the end after every so many steps whatever
+// the route. Those periods are the route's length times different primes,
+// like the puzzle's, which makes their lowest common multiple their product.
+fn generate(rng: &mut Rng, nodes: usize) -> Generated {
+    // the last character of a node that's neither a start nor an end
+    const MIDDLE: &[u8] = b"0123456789BCDEFGHIJKLMNOPQRSTUVWXY";
+    let mut primes = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+    rng.shuffle(&mut primes);
+    let primes = &primes[..6];
+    let length = (nodes / (2 * primes.iter().sum::<usize>())).max(1);
+
+    let mut definitions = Vec::new();
+    let mut middle = 0;
+    for (ghost, &prime) in primes.iter().enumerate() {
+        let (start, end) = match ghost {
+            0 => ("AAA".to_string(), "ZZZ".to_string()),
+            _ => (node_name(ghost, 'A'), node_name(ghost, 'Z')),
+        };
+        let mut lanes = || {
+            let name = node_name(middle / MIDDLE.len(), MIDDLE[middle % MIDDLE.len()] as char);
+            middle += 1;
+            name
+        };
+
+        let period = length * prime;
+        let columns: Vec<(String, String)> = (1..period).map(|_| (lanes(), lanes())).collect();
+        let ends = (end.clone(), end.clone());
+        for (column, (first, second)) in columns.iter().enumerate() {
+            let next = columns.get(column + 1).unwrap_or(&ends);
+            definitions.push((first.clone(), next.clone()));
+            definitions.push((second.clone(), next.clone()));
+        }
+        definitions.push((start, columns[0].clone()));
+        definitions.push((end, columns[0].clone()));
+    }
+    rng.shuffle(&mut definitions);
+
+    let mut input: String = (0..length).map(|_| *rng.choose(&['L', 'R'])).collect();
+    input.push_str("\n\n");
+    for (name, (left, right)) in definitions {
+        input += &format!("{name} = ({left}, {right})\n");
+    }
+
+    let length = length as u64;
+    Generated {
+        input,
+        answers: vec![
+            (1, (length * primes[0] as u64).into()),
+            (2, (length * primes.iter().product::<usize>() as u64).into()),
+        ],
+    }
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    type Parsed = Documents;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        input.parse()
+    }
+
+    fn part1(documents: &Self::Parsed) -> Result<Answer> {
+        Ok(documents.map.camel_route_length(&documents.route)?.into())
+    }
+
+    fn part2(documents: &Self::Parsed) -> Result<Answer> {
+        Ok(documents.map.ghost_route_length(&documents.route)?.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn route(s: &str) -> Vec<Direction> {
+        parse_route(&mut Parser::new(s)).unwrap()
+    }
+
+    #[test]
+    fn test_parse_node() {
+        let node = Node::parse(&mut Parser::new("(BBB, CCC)")).unwrap();
+        assert_eq!(node.left, "BBB");
+        assert_eq!(node.right, "CCC");
+    }
+
+    const TEST_MAP: &str = "\
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+";
+
+    fn neighbours<'a>(map: &'a Map, name: &str) -> (&'a str, &'a str) {
+        let (left, right) = map.nodes[map.names.id(name).unwrap()];
+        (map.names.resolve(left), map.names.resolve(right))
+    }
+
+    #[test]
+    fn test_parse_map() {
+        let map = TEST_MAP.parse::<Map>().unwrap();
+        assert_eq!(neighbours(&map, "AAA"), ("BBB", "BBB"));
+        assert_eq!(neighbours(&map, "BBB"), ("AAA", "ZZZ"));
+        assert_eq!(neighbours(&map, "ZZZ"), ("ZZZ", "ZZZ"));
+    }
+
+    #[test]
+    fn test_camel_route() {
+        let map = TEST_MAP.parse::<Map>().unwrap();
+
+        assert_eq!(map.camel_route_length(&route("LR")).unwrap(), 2);
+        assert_eq!(map.camel_route_length(&route("LLR")).unwrap(), 6);
+    }
+
+    const GHOST_MAP: &str = "\
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+";
+
+    #[test]
+    fn test_ghost_route() {
+        let map = GHOST_MAP.parse::<Map>().unwrap();
+        assert_eq!(map.ghost_route_length(&route("LR")).unwrap(), 6);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = format!("LRXL\n\n{TEST_MAP}")
+            .parse::<Documents>()
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "1:3: expected 'L' or 'R', found 'X'");
+
+        let err = format!("LR\n\n{}", TEST_MAP.replace("(AAA, ZZZ)", "AAA, ZZZ"))
+            .parse::<Documents>()
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "4:7: expected '(', found 'AAA, ZZZ'");
+    }
+
+    #[test]
+    fn test_unknown_node() {
+        let err = "AAA = (BBB, QQQ)\nBBB = (ZZZ, ZZZ)\n"
+            .parse::<Map>()
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "1:13: unknown node: 'QQQ'");
+    }
+
+    #[test]
+    fn test_unreachable_end() {
+        let map = "AAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n"
+            .parse::<Map>()
+            .unwrap();
+        let err = map.camel_route_length(&route("L")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the route from 'AAA' never reaches a node ending in 'Z'"
+        );
+    }
+
+    #[test]
+    fn test_uneven_ghost() {
+        // reaches 11Z after two steps, then again after three more
+        let map = "11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11C, 11C)\n11C = (11D, 11D)\n11D = (11Z, 11Z)\n"
+            .parse::<Map>()
+            .unwrap();
+        let err = map.ghost_route_length(&route("L")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the ghost from '11A' doesn't reach the end at a steady pace"
+        );
+    }
+}
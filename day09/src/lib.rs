@@ -0,0 +1,115 @@
+use aoc_common::{math, Answer, ParseError, Parser, Result, Solution};
+use std::vec::Vec;
+use tracing::trace;
+
+pub struct Sequence {
+    readings: Vec<i32>,
+}
+
+impl Sequence {
+    fn parse(p: &mut Parser) -> Result<Sequence, ParseError> {
+        Ok(Sequence {
+            readings: p.numbers()?,
+        })
+    }
+}
+
+// Taking differences until they're all zero assumes the readings come from
+// a polynomial of degree below their count n, and for those the nth
+// differences vanish: Σ (-1)^(n-i) C(n, i) a_i = 0 over i = 0..=n. Solving
+// that for a_n gives the next reading directly.
+fn extrapolate(sequence: &[i32]) -> Option<i64> {
+    let n = sequence.len() as u64;
+    sequence
+        .iter()
+        .enumerate()
+        .try_fold(0_i64, |next, (i, &reading)| {
+            let weight = i64::try_from(math::binomial(n, i as u64)?).ok()?;
+            let term = weight.checked_mul(reading as i64)?;
+            if (n - 1 - i as u64).is_multiple_of(2) {
+                next.checked_add(term)
+            } else {
+                next.checked_sub(term)
+            }
+        })
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Parsed = Vec<Sequence>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Parser::new(input).each_line(Sequence::parse)
+    }
+
+    fn part1(sequences: &Self::Parsed) -> Result<Answer> {
+        let sum = sequences.iter().try_fold(0_i64, |sum, sequence| {
+            let next = extrapolate(&sequence.readings)?;
+            trace!(next);
+            sum.checked_add(next)
+        });
+        Ok(sum
+            .ok_or("the readings are too large to extrapolate")?
+            .into())
+    }
+
+    fn part2(sequences: &Self::Parsed) -> Result<Answer> {
+        // extrapolating backwards is extrapolating forwards on the reversed readings
+        let sum = sequences.iter().try_fold(0_i64, |sum, sequence| {
+            let mut readings = sequence.readings.clone();
+            readings.reverse();
+            let previous = extrapolate(&readings)?;
+            trace!(previous);
+            sum.checked_add(previous)
+        });
+        Ok(sum
+            .ok_or("the readings are too large to extrapolate")?
+            .into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_sequence() {
+        let sequence = Sequence::parse(&mut Parser::new("0 3 6 9 12 15")).unwrap();
+        assert_eq!(sequence.readings, vec![0, 3, 6, 9, 12, 15]);
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        let sequence = vec![0, 3, 6, 9, 12, 15];
+        assert_eq!(extrapolate(&sequence), Some(18));
+
+        let sequence = vec![1, 3, 6, 10, 15, 21];
+        assert_eq!(extrapolate(&sequence), Some(28));
+
+        let sequence = vec![10, 13, 16, 21, 30, 45];
+        assert_eq!(extrapolate(&sequence), Some(68));
+    }
+
+    #[test]
+    fn test_extrapolate_reverse() {
+        let mut sequence = vec![10, 13, 16, 21, 30, 45];
+        assert_eq!(extrapolate(&sequence), Some(68));
+
+        sequence.reverse();
+        assert_eq!(extrapolate(&sequence), Some(5));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day9::parse("0 3 6 9 12 15\n1 3 six 10 15 21\n")
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "2:5: invalid number 'six': invalid digit found in string"
+        );
+    }
+}
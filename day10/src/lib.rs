@@ -0,0 +1,351 @@
+use aoc_common::geometry::{self, Point};
+use aoc_common::{graph, Answer, Generated, Grid, ParseError, Position, Result, Rng, Solution};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use tracing::debug;
+
+#[derive(PartialEq, Eq)]
+enum Tile {
+    Start,
+    Ground,
+    NorthSouth,
+    EastWest,
+    NorthWest,
+    NorthEast,
+    SouthEast,
+    SouthWest,
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Tile::Start => 'S',
+            Tile::Ground => '.',
+            Tile::NorthSouth => '|',
+            Tile::EastWest => '-',
+            Tile::NorthWest => 'F',
+            Tile::NorthEast => '7',
+            Tile::SouthEast => 'J',
+            Tile::SouthWest => 'L',
+        };
+        write!(f, "{c}")
+    }
+}
+
+impl TryFrom<char> for Tile {
+    type Error = &'static str;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'S' => Ok(Tile::Start),
+            '.' => Ok(Tile::Ground),
+            '|' => Ok(Tile::NorthSouth),
+            '-' => Ok(Tile::EastWest),
+            'F' => Ok(Tile::NorthWest),
+            '7' => Ok(Tile::NorthEast),
+            'J' => Ok(Tile::SouthEast),
+            'L' => Ok(Tile::SouthWest),
+            _ => Err("a pipe, '.' or 'S'"),
+        }
+    }
+}
+
+impl Tile {
+    fn accessible_from_south(&self) -> bool {
+        matches!(
+            self,
+            Tile::Start | Tile::NorthSouth | Tile::NorthWest | Tile::NorthEast
+        )
+    }
+
+    fn accessible_from_north(&self) -> bool {
+        matches!(
+            self,
+            Tile::Start | Tile::NorthSouth | Tile::SouthWest | Tile::SouthEast
+        )
+    }
+
+    fn accessible_from_west(&self) -> bool {
+        matches!(
+            self,
+            Tile::Start | Tile::EastWest | Tile::NorthEast | Tile::SouthEast
+        )
+    }
+
+    fn accessible_from_east(&self) -> bool {
+        matches!(
+            self,
+            Tile::Start | Tile::EastWest | Tile::NorthWest | Tile::SouthWest
+        )
+    }
+}
+
+pub struct Map {
+    tiles: Grid<Tile>,
+}
+
+impl FromStr for Map {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let map = Map {
+            tiles: Grid::parse(s, Tile::try_from)?,
+        };
+
+        match map.find_start() {
+            Some(_) => Ok(map),
+            None => Err(ParseError::unexpected_end(s, "a start tile 'S'")),
+        }
+    }
+}
+
+impl fmt::Display for Map {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.tiles)
+    }
+}
+
+impl Map {
+    fn find_start(&self) -> Option<Position> {
+        self.tiles.find(|tile| *tile == Tile::Start)
+    }
+
+    // whether the pipes at `from` and its neighbour `to` join up
+    fn connects(&self, from: Position, to: Position) -> bool {
+        let (here, there) = (&self.tiles[from], &self.tiles[to]);
+        if to.0 < from.0 {
+            here.accessible_from_north() && there.accessible_from_south()
+        } else if to.0 > from.0 {
+            here.accessible_from_south() && there.accessible_from_north()
+        } else if to.1 < from.1 {
+            here.accessible_from_west() && there.accessible_from_east()
+        } else {
+            here.accessible_from_east() && there.accessible_from_west()
+        }
+    }
+
+    fn exits(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        self.tiles
+            .neighbours4(position)
+            .filter(move |&neighbour| self.connects(position, neighbour))
+    }
+
+    fn distances(&self) -> HashMap<Position, usize> {
+        let start = self.find_start().expect("no start found!");
+        graph::bfs(start, |&position| self.exits(position))
+    }
+
+    fn steps_to_farthest_point(&self) -> usize {
+        let distances = self.distances();
+        debug!(length = distances.len(), "found the loop");
+        *distances.values().max().unwrap()
+    }
+
+    // every tile of the loop, in order around it
+    fn path(&self) -> Vec<Position> {
+        let start = self.find_start().expect("no start found!");
+        graph::dfs(start, |&position| self.exits(position))
+    }
+
+    // tiles inside the loop, treating it as a polygon with a vertex at every
+    // tile along the way
+    fn inner_points(&self) -> Result<u64> {
+        let path = self
+            .path()
+            .into_iter()
+            .map(Point::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        debug!(length = path.len(), "found the loop");
+        Ok(geometry::interior_points(&path).ok_or("the loop doesn't enclose an area")?)
+    }
+}
+
+// a single loop on a grid of about `size` by `size` tiles, with junk pipes
+// around it. The loop is the outline of a region of two-by-two blocks made
+// of one unbroken run of blocks in each column, where neighbouring columns'
+// runs overlap. That keeps the outline from ever touching itself, and makes
+// the answers a matter of counting blocks and where they meet.
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let blocks = (size.saturating_sub(1) / 2).max(1);
+    let mut runs: Vec<(usize, usize)> = Vec::with_capacity(blocks);
+    for _ in 0..blocks {
+        let (top, bottom) = match runs.last() {
+            None => (0, blocks),
+            Some(&(top, bottom)) => (top, bottom),
+        };
+        let new_top = rng.below(bottom as u64) as usize;
+        let new_bottom = rng.range((top.max(new_top) + 1) as u64..(blocks + 1) as u64) as usize;
+        runs.push((new_top, new_bottom));
+    }
+    let inside = |x: usize, y: usize| {
+        // blocks are numbered from 1 here, so 0 is the edge of the grid
+        x > 0
+            && y > 0
+            && runs
+                .get(x - 1)
+                .is_some_and(|&(top, bottom)| (top..bottom).contains(&(y - 1)))
+    };
+
+    // which way each tile's pipe opens, if it's on the loop: corners of
+    // blocks open along any edges between a block inside and one outside,
+    // and the tiles along those edges carry the pipe on
+    let (mut area, mut shared_edges, mut shared_corners) = (0_u64, 0_u64, 0_u64);
+    let side = 2 * blocks + 1;
+    let mut tiles = vec![vec!['.'; side]; side];
+    let mut path = Vec::new();
+    for (row, line) in tiles.iter_mut().enumerate() {
+        for (column, tile) in line.iter_mut().enumerate() {
+            let (x, y) = (column / 2, row / 2);
+            let edge = |a: bool, b: bool| a != b;
+            let pipe = match (row % 2, column % 2) {
+                (0, 0) => {
+                    let north = edge(inside(x, y), inside(x + 1, y));
+                    let south = edge(inside(x, y + 1), inside(x + 1, y + 1));
+                    let west = edge(inside(x, y), inside(x, y + 1));
+                    let east = edge(inside(x + 1, y), inside(x + 1, y + 1));
+                    if [
+                        inside(x, y),
+                        inside(x + 1, y),
+                        inside(x, y + 1),
+                        inside(x + 1, y + 1),
+                    ]
+                    .iter()
+                    .all(|&inside| inside)
+                    {
+                        shared_corners += 1;
+                    }
+                    match (north, south, west, east) {
+                        (true, true, false, false) => Some('|'),
+                        (false, false, true, true) => Some('-'),
+                        (true, false, false, true) => Some('L'),
+                        (true, false, true, false) => Some('J'),
+                        (false, true, true, false) => Some('7'),
+                        (false, true, false, true) => Some('F'),
+                        _ => None,
+                    }
+                }
+                (0, 1) => {
+                    shared_edges += u64::from(inside(x + 1, y) && inside(x + 1, y + 1));
+                    edge(inside(x + 1, y), inside(x + 1, y + 1)).then_some('-')
+                }
+                (1, 0) => {
+                    shared_edges += u64::from(inside(x, y + 1) && inside(x + 1, y + 1));
+                    edge(inside(x, y + 1), inside(x + 1, y + 1)).then_some('|')
+                }
+                _ => {
+                    area += u64::from(inside(x + 1, y + 1));
+                    None
+                }
+            };
+            match pipe {
+                Some(pipe) => {
+                    *tile = pipe;
+                    path.push((row, column));
+                }
+                None if rng.one_in(2) => *tile = *rng.choose(&['|', '-', 'L', 'J', '7', 'F']),
+                None => (),
+            }
+        }
+    }
+
+    // junk next to the start could look like it joins on, so clear it
+    let (row, column) = *rng.choose(&path);
+    tiles[row][column] = 'S';
+    for (row, column) in [
+        (row.wrapping_sub(1), column),
+        (row + 1, column),
+        (row, column.wrapping_sub(1)),
+        (row, column + 1),
+    ] {
+        if let Some(tile) = tiles.get_mut(row).and_then(|line| line.get_mut(column)) {
+            if !path.contains(&(row, column)) {
+                *tile = '.';
+            }
+        }
+    }
+
+    let input = tiles
+        .iter()
+        .map(|line| line.iter().chain(['\n'].iter()).collect::<String>())
+        .collect();
+    Generated {
+        input,
+        answers: vec![
+            (1, (4 * area - 2 * shared_edges).into()),
+            (2, (area + shared_edges + shared_corners).into()),
+        ],
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Parsed = Map;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        input.parse()
+    }
+
+    fn part1(map: &Self::Parsed) -> Result<Answer> {
+        Ok(map.steps_to_farthest_point().into())
+    }
+
+    fn part2(map: &Self::Parsed) -> Result<Answer> {
+        Ok(map.inner_points()?.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TEST: &str = include_str!("../../examples/day10/farthest.txt");
+
+    #[test]
+    fn test_longest_path() {
+        let map: Map = TEST.parse().unwrap();
+        assert_eq!(map.find_start().unwrap(), (2, 0));
+        assert_eq!(map.steps_to_farthest_point(), 8);
+    }
+
+    const AREA_TEST1: &str = include_str!("../../examples/day10/enclosed.txt");
+
+    const AREA_TEST2: &str = include_str!("../../examples/day10/junk.txt");
+
+    #[test]
+    fn test_area() {
+        let map: Map = AREA_TEST1.parse().unwrap();
+        assert_eq!(map.inner_points().unwrap(), 4);
+
+        let map: Map = AREA_TEST2.parse().unwrap();
+        assert_eq!(map.inner_points().unwrap(), 10);
+    }
+
+    #[test]
+    fn test_display() {
+        let map: Map = AREA_TEST2.parse().unwrap();
+        assert_eq!(map.to_string(), AREA_TEST2);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = TEST.replace("SJLL7", "SJL?7").parse::<Map>().err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "3:4: expected a pipe, '.' or 'S', found '?'"
+        );
+
+        let err = TEST.replace('S', "F").parse::<Map>().err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "6:1: expected a start tile 'S', found end of input"
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use aoc_common::{Answer, Generated, Grid, ParseError, Point, Position, Result, Rng, Solution};
+use std::str::FromStr;
+use tracing::debug;
+
+pub struct Space {
+    galaxies: Vec<Position>,
+    is_row_occupied: Vec<bool>,
+    is_column_occupied: Vec<bool>,
+}
+
+impl FromStr for Space {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let image = Grid::parse(s, |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err("'#' or '.'"),
+        })?;
+
+        Ok(Space {
+            galaxies: image
+                .iter()
+                .filter(|(_, &galaxy)| galaxy)
+                .map(|(position, _)| position)
+                .collect(),
+            is_row_occupied: image.rows().map(|row| row.contains(&true)).collect(),
+            is_column_occupied: image
+                .columns()
+                .map(|mut column| column.any(|&galaxy| galaxy))
+                .collect(),
+        })
+    }
+}
+
+// how much extra space each empty row or column gets
+fn offsets(is_occupied: &[bool], expansion: usize) -> Vec<usize> {
+    is_occupied
+        .iter()
+        .scan(0, |offset, occupied| {
+            if !*occupied {
+                *offset += expansion;
+            }
+
+            Some(*offset)
+        })
+        .collect()
+}
+
+impl Space {
+    // each empty row or column becomes `expansion_factor` rows or columns
+    fn expanded(&self, expansion_factor: usize) -> Option<Vec<Point>> {
+        let row_offsets = offsets(&self.is_row_occupied, expansion_factor - 1);
+        let column_offsets = offsets(&self.is_column_occupied, expansion_factor - 1);
+
+        self.galaxies
+            .iter()
+            .map(|&(row, column)| {
+                let position = (
+                    row.checked_add(row_offsets[row])?,
+                    column.checked_add(column_offsets[column])?,
+                );
+                Point::try_from(position).ok()
+            })
+            .collect()
+    }
+
+    // distances along each axis add up separately, and once sorted, each
+    // coordinate is added once for every one before it and taken away once
+    // for every one after, so there's no need to visit every pair
+    fn find_sum_of_shortest_paths(&self, expansion_factor: usize) -> Option<u64> {
+        debug!(galaxies = self.galaxies.len(), expansion_factor);
+        let galaxies = self.expanded(expansion_factor)?;
+        let mut sum: i128 = 0;
+        for axis in [|point: &Point| point.x, |point: &Point| point.y] {
+            let mut coordinates: Vec<i64> = galaxies.iter().map(axis).collect();
+            coordinates.sort_unstable();
+            let count = coordinates.len() as i128;
+            for (i, &coordinate) in coordinates.iter().enumerate() {
+                sum += i128::from(coordinate) * (2 * i as i128 - (count - 1));
+            }
+        }
+        u64::try_from(sum).ok()
+    }
+
+    // measures each pair of galaxies on its own
+    #[cfg(test)]
+    fn find_sum_of_shortest_paths_pairwise(&self, expansion_factor: usize) -> Option<u64> {
+        use itertools::Itertools;
+
+        self.expanded(expansion_factor)?
+            .iter()
+            .tuple_combinations()
+            .try_fold(0_u64, |sum, (a, b)| sum.checked_add(a.manhattan(*b)?))
+    }
+}
+
+// the most galaxies the generator will measure every pair of
+const CHEAP_GALAXIES: usize = 2_000;
+
+// a `size` by `size` image with a galaxy in about one cell in sixteen, and
+// about one row and column in eight left empty to expand
+fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let size = size.max(1);
+    let clear_rows: Vec<bool> = (0..size).map(|_| rng.one_in(8)).collect();
+    let clear_columns: Vec<bool> = (0..size).map(|_| rng.one_in(8)).collect();
+    let mut input = String::new();
+    let mut galaxies = Vec::new();
+    for (row, &clear_row) in clear_rows.iter().enumerate() {
+        for (column, &clear_column) in clear_columns.iter().enumerate() {
+            let galaxy = !clear_row && !clear_column && rng.one_in(16);
+            if galaxy {
+                galaxies.push((row, column));
+            }
+            input.push(if galaxy { '#' } else { '.' });
+        }
+        input.push('\n');
+    }
+
+    // rows and columns left without galaxies by chance expand too
+    let mut empty_rows = vec![true; size];
+    let mut empty_columns = vec![true; size];
+    for &(row, column) in &galaxies {
+        empty_rows[row] = false;
+        empty_columns[column] = false;
+    }
+
+    let mut answers = Vec::new();
+    if galaxies.len() <= CHEAP_GALAXIES {
+        let sum_of_distances = |expansion: u64| -> u64 {
+            let expand = |empty: &[bool]| -> Vec<u64> {
+                empty
+                    .iter()
+                    .scan(0, |position, &empty| {
+                        let here = *position;
+                        *position += if empty { expansion } else { 1 };
+                        Some(here)
+                    })
+                    .collect()
+            };
+            let (rows, columns) = (expand(&empty_rows), expand(&empty_columns));
+
+            let mut total = 0;
+            for (i, &(row_a, column_a)) in galaxies.iter().enumerate() {
+                for &(row_b, column_b) in &galaxies[i + 1..] {
+                    total += rows[row_a].abs_diff(rows[row_b])
+                        + columns[column_a].abs_diff(columns[column_b]);
+                }
+            }
+            total
+        };
+        answers = vec![
+            (1, sum_of_distances(2).into()),
+            (2, sum_of_distances(1_000_000).into()),
+        ];
+    }
+
+    Generated { input, answers }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Parsed = Space;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        input.parse()
+    }
+
+    fn part1(space: &Self::Parsed) -> Result<Answer> {
+        Ok(space
+            .find_sum_of_shortest_paths(2)
+            .ok_or("the distances are too large")?
+            .into())
+    }
+
+    fn part2(space: &Self::Parsed) -> Result<Answer> {
+        Ok(space
+            .find_sum_of_shortest_paths(1_000_000)
+            .ok_or("the distances are too large")?
+            .into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::differential;
+
+    const BASIC_SPACE: &str = include_str!("../../examples/day11/example.txt");
+
+    #[test]
+    fn test_day1() {
+        let space: Space = BASIC_SPACE.parse().unwrap();
+
+        assert_eq!(
+            space.expanded(2).unwrap(),
+            vec![
+                Point::new(4, 0),
+                Point::new(9, 1),
+                Point::new(0, 2),
+                Point::new(8, 5),
+                Point::new(1, 6),
+                Point::new(12, 7),
+                Point::new(9, 10),
+                Point::new(0, 11),
+                Point::new(5, 11)
+            ]
+        );
+
+        assert_eq!(space.find_sum_of_shortest_paths(2), Some(374));
+    }
+
+    #[test]
+    fn test_older_galaxies() {
+        let space: Space = BASIC_SPACE.parse().unwrap();
+
+        assert_eq!(space.find_sum_of_shortest_paths(10), Some(1030));
+        assert_eq!(space.find_sum_of_shortest_paths(100), Some(8410));
+    }
+
+    #[test]
+    fn test_sum_random() {
+        let random_image = |rng: &mut Rng| -> String {
+            let (rows, columns) = (rng.range(1..12), rng.range(1..12));
+            (0..rows)
+                .map(|_| {
+                    let row: String = (0..columns)
+                        .map(|_| if rng.one_in(4) { '#' } else { '.' })
+                        .collect();
+                    row + "\n"
+                })
+                .collect()
+        };
+        let sums = |sum: fn(&Space, usize) -> Option<u64>| {
+            move |image: &String| {
+                let space: Space = image.parse().unwrap();
+                [1, 2, 10, 1_000_000].map(|expansion| sum(&space, expansion))
+            }
+        };
+        differential(
+            200,
+            random_image,
+            sums(Space::find_sum_of_shortest_paths),
+            sums(Space::find_sum_of_shortest_paths_pairwise),
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = "...#\n.x..\n".parse::<Space>().err().unwrap();
+        assert_eq!(err.to_string(), "2:2: expected '#' or '.', found 'x'");
+
+        let err = "...#\n.#...\n".parse::<Space>().err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "2:1: expected a row of 4 cells, found '.#...'"
+        );
+    }
+}